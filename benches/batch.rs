@@ -0,0 +1,62 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use hardware_address::{format_many, parse, parse_many, MacAddr};
+
+const COUNT: usize = 4096;
+
+fn addrs() -> Vec<[u8; 6]> {
+  let mut x = 0x9E37_79B9_7F4A_7C15u64;
+  (0..COUNT)
+    .map(|_| {
+      x ^= x << 13;
+      x ^= x >> 7;
+      x ^= x << 17;
+      let b = x.to_le_bytes();
+      [b[0], b[1], b[2], b[3], b[4], b[5]]
+    })
+    .collect()
+}
+
+fn bench_parse(c: &mut Criterion) {
+  let strings: Vec<String> = addrs()
+    .into_iter()
+    .map(|a| MacAddr::from_raw(a).to_string())
+    .collect();
+  let src: Vec<&str> = strings.iter().map(String::as_str).collect();
+  let mut out = vec![[0u8; 6]; COUNT];
+
+  let mut group = c.benchmark_group("parse");
+  group.throughput(Throughput::Elements(COUNT as u64));
+  group.bench_function(BenchmarkId::new("scalar", COUNT), |b| {
+    b.iter(|| {
+      for (s, o) in src.iter().zip(out.iter_mut()) {
+        *o = parse::<6>(black_box(s.as_bytes())).unwrap();
+      }
+    })
+  });
+  group.bench_function(BenchmarkId::new("parse_many", COUNT), |b| {
+    b.iter(|| parse_many(black_box(&src), &mut out).unwrap())
+  });
+  group.finish();
+}
+
+fn bench_format(c: &mut Criterion) {
+  let addrs = addrs();
+  let mut out = vec![[0u8; 17]; COUNT];
+
+  let mut group = c.benchmark_group("format");
+  group.throughput(Throughput::Elements(COUNT as u64));
+  group.bench_function(BenchmarkId::new("scalar", COUNT), |b| {
+    b.iter(|| {
+      for (a, o) in addrs.iter().zip(out.iter_mut()) {
+        *o = MacAddr::from_raw(*black_box(a)).to_colon_separated_array();
+      }
+    })
+  });
+  group.bench_function(BenchmarkId::new("format_many", COUNT), |b| {
+    b.iter(|| format_many(black_box(&addrs), &mut out))
+  });
+  group.finish();
+}
+
+criterion_group!(benches, bench_parse, bench_format);
+criterion_main!(benches);
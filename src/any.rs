@@ -0,0 +1,139 @@
+use core::fmt;
+
+use crate::{Eui64Addr, InfiniBandAddr, MacAddr};
+
+/// A hardware address of any of the supported sizes.
+///
+/// Protocols like ARP, NDP or netlink carry link-layer addresses whose size
+/// depends on the link type; this picks the address type from the length.
+///
+/// ## Example
+///
+/// ```rust
+/// use hardware_address::{AnyHardwareAddr, MacAddr};
+///
+/// let addr = AnyHardwareAddr::from_bytes(&[0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]).unwrap();
+/// assert_eq!(addr, AnyHardwareAddr::Mac(MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01])));
+/// assert_eq!(addr.size(), 6);
+/// assert_eq!(addr.to_string(), "00:00:5e:00:53:01");
+/// assert!(AnyHardwareAddr::from_bytes(&[0; 7]).is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AnyHardwareAddr {
+  /// A 6-octet MAC-48/EUI-48 address.
+  Mac(MacAddr),
+  /// An 8-octet EUI-64 address.
+  Eui64(Eui64Addr),
+  /// A 20-octet IP over InfiniBand address.
+  InfiniBand(InfiniBandAddr),
+}
+
+impl AnyHardwareAddr {
+  /// Returns the address of the type with `src.len()` octets, or `None` if
+  /// no address type has that size.
+  #[inline]
+  pub fn from_bytes(src: &[u8]) -> Option<Self> {
+    match src.len() {
+      MacAddr::SIZE => MacAddr::try_from(src).ok().map(Self::Mac),
+      Eui64Addr::SIZE => Eui64Addr::try_from(src).ok().map(Self::Eui64),
+      InfiniBandAddr::SIZE => InfiniBandAddr::try_from(src).ok().map(Self::InfiniBand),
+      _ => None,
+    }
+  }
+
+  /// Returns the address as a byte slice.
+  #[inline]
+  pub const fn as_bytes(&self) -> &[u8] {
+    match self {
+      Self::Mac(addr) => addr.as_bytes(),
+      Self::Eui64(addr) => addr.as_bytes(),
+      Self::InfiniBand(addr) => addr.as_bytes(),
+    }
+  }
+
+  /// Returns the size of the address in bytes.
+  #[inline]
+  pub const fn size(&self) -> usize {
+    self.as_bytes().len()
+  }
+
+  /// Returns the all-zero address of the same type.
+  #[inline]
+  pub const fn zeroed(&self) -> Self {
+    match self {
+      Self::Mac(_) => Self::Mac(MacAddr::new()),
+      Self::Eui64(_) => Self::Eui64(Eui64Addr::new()),
+      Self::InfiniBand(_) => Self::InfiniBand(InfiniBandAddr::new()),
+    }
+  }
+
+  /// Returns `true` if every octet is zero.
+  #[inline]
+  pub fn is_zero(&self) -> bool {
+    self.as_bytes().iter().all(|&b| b == 0)
+  }
+}
+
+impl AsRef<[u8]> for AnyHardwareAddr {
+  #[inline]
+  fn as_ref(&self) -> &[u8] {
+    self.as_bytes()
+  }
+}
+
+impl From<MacAddr> for AnyHardwareAddr {
+  #[inline]
+  fn from(addr: MacAddr) -> Self {
+    Self::Mac(addr)
+  }
+}
+
+impl From<Eui64Addr> for AnyHardwareAddr {
+  #[inline]
+  fn from(addr: Eui64Addr) -> Self {
+    Self::Eui64(addr)
+  }
+}
+
+impl From<InfiniBandAddr> for AnyHardwareAddr {
+  #[inline]
+  fn from(addr: InfiniBandAddr) -> Self {
+    Self::InfiniBand(addr)
+  }
+}
+
+/// Formats the address like the wrapped type does.
+impl fmt::Display for AnyHardwareAddr {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Mac(addr) => addr.fmt(f),
+      Self::Eui64(addr) => addr.fmt(f),
+      Self::InfiniBand(addr) => addr.fmt(f),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use std::format;
+
+  #[test]
+  fn sizes() {
+    for size in [6, 8, 20] {
+      let addr = AnyHardwareAddr::from_bytes(&[0xab; 20][..size]).unwrap();
+      assert_eq!(addr.size(), size);
+      assert_eq!(addr.as_bytes(), &[0xab; 20][..size]);
+      assert!(!addr.is_zero());
+      assert_eq!(addr.zeroed().size(), size);
+      assert!(addr.zeroed().is_zero());
+    }
+    assert!(AnyHardwareAddr::from_bytes(&[]).is_none());
+    assert!(AnyHardwareAddr::from_bytes(&[0; 16]).is_none());
+
+    let eui = AnyHardwareAddr::from(Eui64Addr::from_raw([2, 0, 0x5e, 0x10, 0, 0, 0, 1]));
+    assert_eq!(format!("{eui:#}"), "02-00-5e-10-00-00-00-01");
+  }
+}
@@ -0,0 +1,658 @@
+//! Address Resolution Protocol ([RFC 826]) packets for IPv4, over Ethernet
+//! and IP over InfiniBand ([RFC 4391]).
+//!
+//! [`ArpPacket`] is a zero-copy view of a packet with any hardware and
+//! protocol address length, [`ArpPacketBuilder`] writes IPv4 packets and
+//! has constructors for requests, replies, gratuitous ARP and the probes of
+//! IPv4 address conflict detection ([RFC 5227]).
+//!
+//! [RFC 826]: https://www.rfc-editor.org/rfc/rfc826
+//! [RFC 4391]: https://www.rfc-editor.org/rfc/rfc4391
+//! [RFC 5227]: https://www.rfc-editor.org/rfc/rfc5227
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hardware_address::{arp::{ArpOperation, ArpPacket, ArpPacketBuilder}, AnyHardwareAddr, MacAddr};
+//!
+//! let mac = MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]);
+//! let mut buf = [0u8; 28];
+//! ArpPacketBuilder::gratuitous(mac.into(), [192, 0, 2, 1]).write(&mut buf).unwrap();
+//!
+//! let packet = ArpPacket::parse(&buf).unwrap();
+//! assert_eq!(packet.operation(), ArpOperation::Request);
+//! assert_eq!(packet.sender_hardware_addr(), Some(AnyHardwareAddr::Mac(mac)));
+//! assert!(packet.is_gratuitous());
+//! ```
+
+use crate::{ethernet::EtherType, AnyHardwareAddr};
+
+/// An error returned while reading or writing an ARP packet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+pub enum ArpError {
+  /// The buffer is too short for the packet.
+  #[error("buffer too short: expected at least {expected} bytes, but got {actual} bytes")]
+  TooShort {
+    /// The number of bytes the packet needs.
+    expected: usize,
+    /// The length of the buffer.
+    actual: usize,
+  },
+  /// The sender and target hardware addresses of a packet to write have
+  /// different sizes.
+  #[error(
+    "hardware address size mismatch: sender is {sender} bytes, but target is {target} bytes"
+  )]
+  HardwareSizeMismatch {
+    /// The size of the sender hardware address.
+    sender: usize,
+    /// The size of the target hardware address.
+    target: usize,
+  },
+}
+
+/// An ARP hardware type, as assigned by IANA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum HardwareType {
+  /// Ethernet (10Mb), used by all Ethernet links.
+  Ethernet,
+  /// IEEE 802 networks.
+  Ieee802,
+  /// EUI-64 addresses.
+  Eui64,
+  /// IP over InfiniBand ([RFC 4391]).
+  ///
+  /// [RFC 4391]: https://www.rfc-editor.org/rfc/rfc4391
+  InfiniBand,
+  /// Any other hardware type.
+  Other(u16),
+}
+
+impl HardwareType {
+  /// Returns the hardware type of `value`.
+  #[inline]
+  pub const fn from_u16(value: u16) -> Self {
+    match value {
+      1 => Self::Ethernet,
+      6 => Self::Ieee802,
+      27 => Self::Eui64,
+      32 => Self::InfiniBand,
+      other => Self::Other(other),
+    }
+  }
+
+  /// Returns the numeric value.
+  #[inline]
+  pub const fn to_u16(self) -> u16 {
+    match self {
+      Self::Ethernet => 1,
+      Self::Ieee802 => 6,
+      Self::Eui64 => 27,
+      Self::InfiniBand => 32,
+      Self::Other(other) => other,
+    }
+  }
+
+  /// Returns the usual hardware type of links with addresses like `addr`.
+  #[inline]
+  pub const fn of(addr: &AnyHardwareAddr) -> Self {
+    match addr {
+      AnyHardwareAddr::Mac(_) => Self::Ethernet,
+      AnyHardwareAddr::Eui64(_) => Self::Eui64,
+      AnyHardwareAddr::InfiniBand(_) => Self::InfiniBand,
+    }
+  }
+}
+
+impl From<u16> for HardwareType {
+  #[inline]
+  fn from(value: u16) -> Self {
+    Self::from_u16(value)
+  }
+}
+
+impl From<HardwareType> for u16 {
+  #[inline]
+  fn from(value: HardwareType) -> Self {
+    value.to_u16()
+  }
+}
+
+/// The operation of an ARP packet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum ArpOperation {
+  /// A request.
+  Request,
+  /// A reply.
+  Reply,
+  /// Any other operation, e.g. of RARP.
+  Other(u16),
+}
+
+impl ArpOperation {
+  /// Returns the operation of `value`.
+  #[inline]
+  pub const fn from_u16(value: u16) -> Self {
+    match value {
+      1 => Self::Request,
+      2 => Self::Reply,
+      other => Self::Other(other),
+    }
+  }
+
+  /// Returns the numeric value.
+  #[inline]
+  pub const fn to_u16(self) -> u16 {
+    match self {
+      Self::Request => 1,
+      Self::Reply => 2,
+      Self::Other(other) => other,
+    }
+  }
+}
+
+impl From<u16> for ArpOperation {
+  #[inline]
+  fn from(value: u16) -> Self {
+    Self::from_u16(value)
+  }
+}
+
+impl From<ArpOperation> for u16 {
+  #[inline]
+  fn from(value: ArpOperation) -> Self {
+    value.to_u16()
+  }
+}
+
+/// The length of the fixed part of an ARP packet.
+const FIXED_LEN: usize = 8;
+
+/// The length of an IPv4 address.
+const IPV4_LEN: usize = 4;
+
+/// A zero-copy view of an ARP packet.
+///
+/// The hardware and protocol address lengths are taken from the packet, so
+/// any link and protocol can be read; the typed accessors return `None`
+/// where the addresses are not of a supported size or not IPv4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArpPacket<'a> {
+  packet: &'a [u8],
+}
+
+impl<'a> ArpPacket<'a> {
+  /// Parses the packet at the start of `buf`. Trailing bytes, e.g. Ethernet
+  /// padding, are ignored.
+  pub fn parse(buf: &'a [u8]) -> Result<Self, ArpError> {
+    let expected = if buf.len() < FIXED_LEN {
+      FIXED_LEN
+    } else {
+      FIXED_LEN + 2 * (buf[4] as usize + buf[5] as usize)
+    };
+    if buf.len() < expected {
+      return Err(ArpError::TooShort {
+        expected,
+        actual: buf.len(),
+      });
+    }
+    Ok(Self {
+      packet: &buf[..expected],
+    })
+  }
+
+  #[inline]
+  fn u16_at(&self, at: usize) -> u16 {
+    u16::from_be_bytes([self.packet[at], self.packet[at + 1]])
+  }
+
+  /// Returns the hardware type.
+  #[inline]
+  pub fn hardware_type(&self) -> HardwareType {
+    HardwareType::from_u16(self.u16_at(0))
+  }
+
+  /// Returns the protocol type.
+  #[inline]
+  pub fn protocol_type(&self) -> EtherType {
+    EtherType::from_u16(self.u16_at(2))
+  }
+
+  /// Returns the length of the hardware addresses.
+  #[inline]
+  pub fn hardware_len(&self) -> usize {
+    self.packet[4] as usize
+  }
+
+  /// Returns the length of the protocol addresses.
+  #[inline]
+  pub fn protocol_len(&self) -> usize {
+    self.packet[5] as usize
+  }
+
+  /// Returns the operation.
+  #[inline]
+  pub fn operation(&self) -> ArpOperation {
+    ArpOperation::from_u16(self.u16_at(6))
+  }
+
+  #[inline]
+  fn field(&self, index: usize) -> &'a [u8] {
+    let (hlen, plen) = (self.hardware_len(), self.protocol_len());
+    let start = FIXED_LEN + (index / 2) * (hlen + plen) + (index % 2) * hlen;
+    let len = if index % 2 == 0 { hlen } else { plen };
+    &self.packet[start..start + len]
+  }
+
+  /// Returns the sender hardware address bytes.
+  #[inline]
+  pub fn sender_hardware_bytes(&self) -> &'a [u8] {
+    self.field(0)
+  }
+
+  /// Returns the sender protocol address bytes.
+  #[inline]
+  pub fn sender_protocol_bytes(&self) -> &'a [u8] {
+    self.field(1)
+  }
+
+  /// Returns the target hardware address bytes.
+  #[inline]
+  pub fn target_hardware_bytes(&self) -> &'a [u8] {
+    self.field(2)
+  }
+
+  /// Returns the target protocol address bytes.
+  #[inline]
+  pub fn target_protocol_bytes(&self) -> &'a [u8] {
+    self.field(3)
+  }
+
+  /// Returns the sender hardware address, or `None` if its size is not
+  /// supported.
+  #[inline]
+  pub fn sender_hardware_addr(&self) -> Option<AnyHardwareAddr> {
+    AnyHardwareAddr::from_bytes(self.sender_hardware_bytes())
+  }
+
+  /// Returns the target hardware address, or `None` if its size is not
+  /// supported.
+  #[inline]
+  pub fn target_hardware_addr(&self) -> Option<AnyHardwareAddr> {
+    AnyHardwareAddr::from_bytes(self.target_hardware_bytes())
+  }
+
+  /// Returns the sender IPv4 address, or `None` if the packet is not for
+  /// IPv4.
+  #[inline]
+  pub fn sender_ipv4(&self) -> Option<[u8; 4]> {
+    self.ipv4(self.sender_protocol_bytes())
+  }
+
+  /// Returns the target IPv4 address, or `None` if the packet is not for
+  /// IPv4.
+  #[inline]
+  pub fn target_ipv4(&self) -> Option<[u8; 4]> {
+    self.ipv4(self.target_protocol_bytes())
+  }
+
+  #[inline]
+  fn ipv4(&self, field: &[u8]) -> Option<[u8; 4]> {
+    match self.protocol_type() {
+      EtherType::Ipv4 => field.try_into().ok(),
+      _ => None,
+    }
+  }
+
+  /// Returns `true` for a gratuitous ARP request or reply: the sender and
+  /// target protocol addresses are the same, and not all zero.
+  ///
+  /// An [RFC 5227] announcement is a gratuitous request.
+  ///
+  /// [RFC 5227]: https://www.rfc-editor.org/rfc/rfc5227
+  #[inline]
+  pub fn is_gratuitous(&self) -> bool {
+    let spa = self.sender_protocol_bytes();
+    spa == self.target_protocol_bytes() && spa.iter().any(|&b| b != 0)
+  }
+
+  /// Returns `true` for an [RFC 5227] probe: a request with an all-zero
+  /// sender protocol address and target hardware address.
+  ///
+  /// [RFC 5227]: https://www.rfc-editor.org/rfc/rfc5227
+  #[inline]
+  pub fn is_probe(&self) -> bool {
+    self.operation() == ArpOperation::Request
+      && self.sender_protocol_bytes().iter().all(|&b| b == 0)
+      && self.target_hardware_bytes().iter().all(|&b| b == 0)
+  }
+
+  /// Returns the bytes of the packet, without trailing bytes of the buffer.
+  #[inline]
+  pub fn as_bytes(&self) -> &'a [u8] {
+    self.packet
+  }
+
+  /// Returns a builder for the same packet, or `None` if it is not for
+  /// IPv4 or its hardware addresses are not of a supported size.
+  pub fn to_builder(&self) -> Option<ArpPacketBuilder> {
+    Some(
+      ArpPacketBuilder::new(
+        self.operation(),
+        self.sender_hardware_addr()?,
+        self.sender_ipv4()?,
+        self.target_hardware_addr()?,
+        self.target_ipv4()?,
+      )
+      .with_hardware_type(self.hardware_type()),
+    )
+  }
+}
+
+/// Builds an ARP packet for IPv4.
+///
+/// The hardware type defaults to the usual one of the sender hardware
+/// address, see [`HardwareType::of`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArpPacketBuilder {
+  hardware_type: HardwareType,
+  operation: ArpOperation,
+  sender_hardware_addr: AnyHardwareAddr,
+  sender_ipv4: [u8; 4],
+  target_hardware_addr: AnyHardwareAddr,
+  target_ipv4: [u8; 4],
+}
+
+impl ArpPacketBuilder {
+  /// Creates a packet from all its fields.
+  #[inline]
+  pub const fn new(
+    operation: ArpOperation,
+    sender_hardware_addr: AnyHardwareAddr,
+    sender_ipv4: [u8; 4],
+    target_hardware_addr: AnyHardwareAddr,
+    target_ipv4: [u8; 4],
+  ) -> Self {
+    Self {
+      hardware_type: HardwareType::of(&sender_hardware_addr),
+      operation,
+      sender_hardware_addr,
+      sender_ipv4,
+      target_hardware_addr,
+      target_ipv4,
+    }
+  }
+
+  /// Creates a request asking for the hardware address of `target_ipv4`.
+  #[inline]
+  pub const fn request(
+    sender_hardware_addr: AnyHardwareAddr,
+    sender_ipv4: [u8; 4],
+    target_ipv4: [u8; 4],
+  ) -> Self {
+    Self::new(
+      ArpOperation::Request,
+      sender_hardware_addr,
+      sender_ipv4,
+      sender_hardware_addr.zeroed(),
+      target_ipv4,
+    )
+  }
+
+  /// Creates a reply telling the requester at `target_hardware_addr` and
+  /// `target_ipv4` that `sender_ipv4` is at `sender_hardware_addr`.
+  #[inline]
+  pub const fn reply(
+    sender_hardware_addr: AnyHardwareAddr,
+    sender_ipv4: [u8; 4],
+    target_hardware_addr: AnyHardwareAddr,
+    target_ipv4: [u8; 4],
+  ) -> Self {
+    Self::new(
+      ArpOperation::Reply,
+      sender_hardware_addr,
+      sender_ipv4,
+      target_hardware_addr,
+      target_ipv4,
+    )
+  }
+
+  /// Creates a gratuitous ARP request announcing that `ipv4` is at
+  /// `hardware_addr`, which is also an [RFC 5227] announcement.
+  ///
+  /// [RFC 5227]: https://www.rfc-editor.org/rfc/rfc5227
+  #[inline]
+  pub const fn gratuitous(hardware_addr: AnyHardwareAddr, ipv4: [u8; 4]) -> Self {
+    Self::request(hardware_addr, ipv4, ipv4)
+  }
+
+  /// Creates an [RFC 5227] probe, checking whether `target_ipv4` is in use
+  /// before claiming it.
+  ///
+  /// [RFC 5227]: https://www.rfc-editor.org/rfc/rfc5227
+  #[inline]
+  pub const fn probe(hardware_addr: AnyHardwareAddr, target_ipv4: [u8; 4]) -> Self {
+    Self::request(hardware_addr, [0; 4], target_ipv4)
+  }
+
+  /// Sets the hardware type.
+  #[inline]
+  pub const fn with_hardware_type(mut self, hardware_type: HardwareType) -> Self {
+    self.hardware_type = hardware_type;
+    self
+  }
+
+  /// Sets the operation.
+  #[inline]
+  pub const fn with_operation(mut self, operation: ArpOperation) -> Self {
+    self.operation = operation;
+    self
+  }
+
+  /// Returns the hardware type.
+  #[inline]
+  pub const fn hardware_type(&self) -> HardwareType {
+    self.hardware_type
+  }
+
+  /// Returns the operation.
+  #[inline]
+  pub const fn operation(&self) -> ArpOperation {
+    self.operation
+  }
+
+  /// Returns the sender hardware address.
+  #[inline]
+  pub const fn sender_hardware_addr(&self) -> AnyHardwareAddr {
+    self.sender_hardware_addr
+  }
+
+  /// Returns the sender IPv4 address.
+  #[inline]
+  pub const fn sender_ipv4(&self) -> [u8; 4] {
+    self.sender_ipv4
+  }
+
+  /// Returns the target hardware address.
+  #[inline]
+  pub const fn target_hardware_addr(&self) -> AnyHardwareAddr {
+    self.target_hardware_addr
+  }
+
+  /// Returns the target IPv4 address.
+  #[inline]
+  pub const fn target_ipv4(&self) -> [u8; 4] {
+    self.target_ipv4
+  }
+
+  /// Returns the length of the packet.
+  #[inline]
+  pub const fn packet_len(&self) -> usize {
+    FIXED_LEN + 2 * (self.sender_hardware_addr.size() + IPV4_LEN)
+  }
+
+  /// Writes the packet to the start of `buf`, returning its length.
+  pub fn write(&self, buf: &mut [u8]) -> Result<usize, ArpError> {
+    let hlen = self.sender_hardware_addr.size();
+    if self.target_hardware_addr.size() != hlen {
+      return Err(ArpError::HardwareSizeMismatch {
+        sender: hlen,
+        target: self.target_hardware_addr.size(),
+      });
+    }
+    let len = self.packet_len();
+    if buf.len() < len {
+      return Err(ArpError::TooShort {
+        expected: len,
+        actual: buf.len(),
+      });
+    }
+
+    buf[..2].copy_from_slice(&self.hardware_type.to_u16().to_be_bytes());
+    buf[2..4].copy_from_slice(&EtherType::Ipv4.to_u16().to_be_bytes());
+    buf[4] = hlen as u8;
+    buf[5] = IPV4_LEN as u8;
+    buf[6..8].copy_from_slice(&self.operation.to_u16().to_be_bytes());
+    let mut at = FIXED_LEN;
+    for field in [
+      self.sender_hardware_addr.as_bytes(),
+      &self.sender_ipv4,
+      self.target_hardware_addr.as_bytes(),
+      &self.target_ipv4,
+    ] {
+      buf[at..at + field.len()].copy_from_slice(field);
+      at += field.len();
+    }
+    Ok(len)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{InfiniBandAddr, MacAddr};
+
+  const MAC: MacAddr = MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]);
+
+  #[test]
+  fn ethernet_request() {
+    // A request for 192.0.2.2 padded to the minimum Ethernet payload.
+    let mut frame = [0u8; 46];
+    frame[..28].copy_from_slice(&[
+      0x00, 0x01, 0x08, 0x00, 0x06, 0x04, 0x00, 0x01, 0x00, 0x00, 0x5e, 0x00, 0x53, 0x01, 192, 0,
+      2, 1, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 192, 0, 2, 2,
+    ]);
+    let packet = ArpPacket::parse(&frame).unwrap();
+    assert_eq!(packet.as_bytes().len(), 28);
+    assert_eq!(packet.hardware_type(), HardwareType::Ethernet);
+    assert_eq!(packet.protocol_type(), EtherType::Ipv4);
+    assert_eq!((packet.hardware_len(), packet.protocol_len()), (6, 4));
+    assert_eq!(packet.operation(), ArpOperation::Request);
+    assert_eq!(packet.sender_hardware_addr(), Some(MAC.into()));
+    assert_eq!(packet.sender_ipv4(), Some([192, 0, 2, 1]));
+    assert_eq!(packet.target_ipv4(), Some([192, 0, 2, 2]));
+    assert!(!packet.is_gratuitous() && !packet.is_probe());
+
+    let builder = ArpPacketBuilder::request(MAC.into(), [192, 0, 2, 1], [192, 0, 2, 2]);
+    assert_eq!(packet.to_builder(), Some(builder));
+    let mut buf = [0u8; 28];
+    assert_eq!(builder.write(&mut buf), Ok(28));
+    assert_eq!(buf, frame[..28]);
+  }
+
+  #[test]
+  fn ipoib_reply() {
+    let sender = InfiniBandAddr::from_raw([
+      0x00, 0x00, 0x00, 0x48, 0xfe, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0xc9,
+      0x03, 0x00, 0x0a, 0x0b, 0x0c,
+    ]);
+    let target = InfiniBandAddr::from_raw([0x11; 20]);
+    let builder =
+      ArpPacketBuilder::reply(sender.into(), [10, 0, 0, 1], target.into(), [10, 0, 0, 2]);
+    assert_eq!(builder.hardware_type(), HardwareType::InfiniBand);
+    assert_eq!(builder.packet_len(), 56);
+
+    let mut buf = [0u8; 56];
+    assert_eq!(builder.write(&mut buf), Ok(56));
+    assert_eq!(buf[..8], [0x00, 0x20, 0x08, 0x00, 20, 4, 0x00, 0x02]);
+
+    let packet = ArpPacket::parse(&buf).unwrap();
+    assert_eq!(packet.hardware_type(), HardwareType::InfiniBand);
+    assert_eq!(
+      packet.sender_hardware_addr(),
+      Some(AnyHardwareAddr::InfiniBand(sender))
+    );
+    assert_eq!(packet.target_hardware_addr(), Some(target.into()));
+    assert_eq!(packet.to_builder(), Some(builder));
+  }
+
+  #[test]
+  fn gratuitous_and_probe() {
+    let mut buf = [0u8; 28];
+    ArpPacketBuilder::gratuitous(MAC.into(), [192, 0, 2, 1])
+      .write(&mut buf)
+      .unwrap();
+    let packet = ArpPacket::parse(&buf).unwrap();
+    assert!(packet.is_gratuitous() && !packet.is_probe());
+
+    ArpPacketBuilder::probe(MAC.into(), [192, 0, 2, 1])
+      .write(&mut buf)
+      .unwrap();
+    let packet = ArpPacket::parse(&buf).unwrap();
+    assert!(packet.is_probe() && !packet.is_gratuitous());
+    assert_eq!(packet.sender_ipv4(), Some([0; 4]));
+    assert!(packet.target_hardware_addr().unwrap().is_zero());
+  }
+
+  #[test]
+  fn other_protocols() {
+    // An IPv6-sized protocol address, as used by some non-IP stacks.
+    let mut buf = [0u8; 8 + 2 * (6 + 16)];
+    buf[..8].copy_from_slice(&[0x00, 0x01, 0x86, 0xdd, 6, 16, 0x00, 0x02]);
+    let packet = ArpPacket::parse(&buf).unwrap();
+    assert_eq!(packet.sender_protocol_bytes().len(), 16);
+    assert_eq!(packet.target_hardware_bytes().len(), 6);
+    assert_eq!(packet.sender_ipv4(), None);
+    assert_eq!(packet.to_builder(), None);
+  }
+
+  #[test]
+  fn errors() {
+    assert_eq!(
+      ArpPacket::parse(&[0; 7]),
+      Err(ArpError::TooShort {
+        expected: 8,
+        actual: 7
+      })
+    );
+    let mut buf = [0u8; 27];
+    buf[..8].copy_from_slice(&[0x00, 0x01, 0x08, 0x00, 6, 4, 0x00, 0x01]);
+    assert_eq!(
+      ArpPacket::parse(&buf),
+      Err(ArpError::TooShort {
+        expected: 28,
+        actual: 27
+      })
+    );
+
+    let builder = ArpPacketBuilder::request(MAC.into(), [0; 4], [0; 4]);
+    assert_eq!(
+      builder.write(&mut buf),
+      Err(ArpError::TooShort {
+        expected: 28,
+        actual: 27
+      })
+    );
+    let mismatched =
+      ArpPacketBuilder::reply(MAC.into(), [0; 4], InfiniBandAddr::new().into(), [0; 4]);
+    assert_eq!(
+      mismatched.write(&mut [0; 64]),
+      Err(ArpError::HardwareSizeMismatch {
+        sender: 6,
+        target: 20
+      })
+    );
+  }
+}
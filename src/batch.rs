@@ -0,0 +1,968 @@
+use crate::{__private::HEX_DIGITS, parse, AddrParseError};
+
+/// An error returned by [`parse_many`]: the index of the first input that
+/// failed to parse, and why.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+#[error("address {index}: {error}")]
+pub struct BatchParseError {
+  index: usize,
+  error: AddrParseError,
+}
+
+impl BatchParseError {
+  /// Creates an error for the input at `index`.
+  #[inline]
+  pub const fn new(index: usize, error: AddrParseError) -> Self {
+    Self { index, error }
+  }
+
+  /// Returns the index of the input that failed to parse.
+  #[inline]
+  pub const fn index(&self) -> usize {
+    self.index
+  }
+
+  /// Returns why the input failed to parse.
+  #[inline]
+  pub const fn error(&self) -> AddrParseError {
+    self.error
+  }
+}
+
+/// Parses every string of `src` into the same position of `out`.
+///
+/// Accepts the same input as [`parse`], with the same result, and stops at
+/// the first input that does not parse. Colon- and hyphen-separated 6-octet
+/// addresses, the bulk of most logs, take a SIMD fast path: AVX2 or SSE4.1
+/// on `x86_64`, selected at runtime with the `std` feature and at compile
+/// time (`target_feature`) without it, and NEON on `aarch64`. Everything else
+/// goes through the scalar parser.
+///
+/// ## Panics
+///
+/// Panics if `src` and `out` have different lengths.
+///
+/// ## Example
+///
+/// ```rust
+/// use hardware_address::{parse_many, MacAddr};
+///
+/// let src = ["00:00:5e:00:53:01", "00-00-5e-00-53-02", "0000.5e00.5303"];
+/// let mut out = [[0u8; 6]; 3];
+/// parse_many(&src, &mut out).unwrap();
+/// assert_eq!(out[2], [0x00, 0x00, 0x5e, 0x00, 0x53, 0x03]);
+///
+/// // Or typed:
+/// let mut addrs = [MacAddr::new(); 3];
+/// MacAddr::parse_many(&src, &mut addrs).unwrap();
+///
+/// let err = parse_many(&["00:00:5e:00:53:01", "nope"], &mut [[0u8; 6]; 2]).unwrap_err();
+/// assert_eq!(err.index(), 1);
+/// ```
+pub fn parse_many<const N: usize>(
+  src: &[&str],
+  out: &mut [[u8; N]],
+) -> Result<(), BatchParseError> {
+  assert_eq!(
+    src.len(),
+    out.len(),
+    "`src` and `out` must have the same length"
+  );
+  parse_many_by(src, |i, addr| out[i] = addr)
+}
+
+/// Formats every address of `addrs` in the colon-separated form (as
+/// `to_colon_separated_array` does) into the same position of `out`.
+///
+/// 6-octet addresses take the same SIMD fast paths as [`parse_many`].
+///
+/// ## Panics
+///
+/// Panics if `addrs` and `out` have different lengths, or if `L` is not
+/// `N * 3 - 1`.
+///
+/// ## Example
+///
+/// ```rust
+/// use hardware_address::{format_many, MacAddr};
+///
+/// let addrs = [[0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]; 2];
+/// let mut out = [[0u8; 17]; 2];
+/// format_many(&addrs, &mut out);
+/// assert_eq!(&out[1], b"00:00:5e:00:53:01");
+///
+/// // Or typed:
+/// let addrs = [MacAddr::from_raw([0xff; 6])];
+/// let mut out = [[0u8; 17]; 1];
+/// MacAddr::format_many(&addrs, &mut out);
+/// assert_eq!(&out[0], b"ff:ff:ff:ff:ff:ff");
+/// ```
+pub fn format_many<const N: usize, const L: usize>(addrs: &[[u8; N]], out: &mut [[u8; L]]) {
+  assert_eq!(
+    addrs.len(),
+    out.len(),
+    "`addrs` and `out` must have the same length"
+  );
+  format_many_by::<N, L, _>(addrs.len(), |i| addrs[i], out)
+}
+
+/// Drives [`parse_many`], handing every parsed address to `f` along with its
+/// index.
+#[doc(hidden)]
+pub fn parse_many_by<const N: usize, F>(src: &[&str], f: F) -> Result<(), BatchParseError>
+where
+  F: FnMut(usize, [u8; N]),
+{
+  Backend::detect().parse_many(src, f)
+}
+
+/// Drives [`format_many`], reading the address at index `i` with `get(i)`.
+#[doc(hidden)]
+pub fn format_many_by<const N: usize, const L: usize, F>(len: usize, get: F, out: &mut [[u8; L]])
+where
+  F: Fn(usize) -> [u8; N],
+{
+  // Checked so that `N == 0` reaches the message instead of underflowing.
+  assert_eq!(
+    N.checked_mul(3).and_then(|l| l.checked_sub(1)),
+    Some(L),
+    "`L` must be `N * 3 - 1`"
+  );
+  assert!(out.len() >= len, "`out` is too short");
+  Backend::detect().format_many(len, get, out)
+}
+
+/// Length of a colon- or hyphen-separated 6-octet address.
+const COLON6_LEN: usize = 17;
+
+/// The instruction set used for 6-octet addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backend {
+  Scalar,
+  #[cfg(target_arch = "x86_64")]
+  Sse41,
+  #[cfg(target_arch = "x86_64")]
+  Avx2,
+  #[cfg(target_arch = "aarch64")]
+  Neon,
+}
+
+impl Backend {
+  #[cfg(all(target_arch = "x86_64", feature = "std"))]
+  fn detect() -> Self {
+    if !std::is_x86_feature_detected!("sse4.1") {
+      Self::Scalar
+    } else if std::is_x86_feature_detected!("avx2") {
+      Self::Avx2
+    } else {
+      Self::Sse41
+    }
+  }
+
+  #[cfg(all(target_arch = "x86_64", not(feature = "std")))]
+  fn detect() -> Self {
+    if cfg!(all(target_feature = "sse4.1", target_feature = "avx2")) {
+      Self::Avx2
+    } else if cfg!(target_feature = "sse4.1") {
+      Self::Sse41
+    } else {
+      Self::Scalar
+    }
+  }
+
+  #[cfg(target_arch = "aarch64")]
+  fn detect() -> Self {
+    if cfg!(target_feature = "neon") {
+      Self::Neon
+    } else {
+      Self::Scalar
+    }
+  }
+
+  #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+  fn detect() -> Self {
+    Self::Scalar
+  }
+
+  /// Returns every backend the running CPU supports, for tests and
+  /// benchmarks.
+  #[cfg(test)]
+  fn available() -> std::vec::Vec<Self> {
+    let mut all = std::vec![Self::Scalar];
+    match Self::detect() {
+      Self::Scalar => {}
+      #[cfg(target_arch = "x86_64")]
+      Self::Sse41 => all.push(Self::Sse41),
+      #[cfg(target_arch = "x86_64")]
+      Self::Avx2 => all.extend([Self::Sse41, Self::Avx2]),
+      #[cfg(target_arch = "aarch64")]
+      Self::Neon => all.push(Self::Neon),
+    }
+    all
+  }
+
+  /// Runs the single and the pair parsing kernel on `a` and `b`.
+  #[cfg(test)]
+  fn parse_kernels(self, a: &[u8; COLON6_LEN], b: &[u8; COLON6_LEN]) -> [Option<[u8; 6]>; 3] {
+    unsafe fn run<K: Kernel>(a: &[u8; COLON6_LEN], b: &[u8; COLON6_LEN]) -> [Option<[u8; 6]>; 3] {
+      let (x, y) = K::parse2(a, b);
+      [K::parse(a), x, y]
+    }
+
+    // SAFETY: `available` only returns backends the CPU supports.
+    unsafe {
+      match self {
+        Self::Scalar => run::<Scalar>(a, b),
+        #[cfg(target_arch = "x86_64")]
+        Self::Sse41 => run::<x86::Sse41>(a, b),
+        #[cfg(target_arch = "x86_64")]
+        Self::Avx2 => run::<x86::Avx2>(a, b),
+        #[cfg(target_arch = "aarch64")]
+        Self::Neon => run::<neon::Neon>(a, b),
+      }
+    }
+  }
+
+  /// Runs the single and the pair formatting kernel on `a` and `b`.
+  #[cfg(test)]
+  fn format_kernels(self, a: &[u8; 6], b: &[u8; 6]) -> [[u8; COLON6_LEN]; 3] {
+    unsafe fn run<K: Kernel>(a: &[u8; 6], b: &[u8; 6]) -> [[u8; COLON6_LEN]; 3] {
+      let (x, y) = K::format2(a, b);
+      [K::format(a), x, y]
+    }
+
+    // SAFETY: `available` only returns backends the CPU supports.
+    unsafe {
+      match self {
+        Self::Scalar => run::<Scalar>(a, b),
+        #[cfg(target_arch = "x86_64")]
+        Self::Sse41 => run::<x86::Sse41>(a, b),
+        #[cfg(target_arch = "x86_64")]
+        Self::Avx2 => run::<x86::Avx2>(a, b),
+        #[cfg(target_arch = "aarch64")]
+        Self::Neon => run::<neon::Neon>(a, b),
+      }
+    }
+  }
+
+  fn parse_many<const N: usize, F>(self, src: &[&str], f: F) -> Result<(), BatchParseError>
+  where
+    F: FnMut(usize, [u8; N]),
+  {
+    // SAFETY: every backend but `Scalar` is only selected if the CPU
+    // supports its instruction set.
+    unsafe {
+      match self {
+        Self::Scalar => parse_loop::<Scalar, N, F>(src, f),
+        #[cfg(target_arch = "x86_64")]
+        Self::Sse41 => x86::parse_many_sse41(src, f),
+        #[cfg(target_arch = "x86_64")]
+        Self::Avx2 => x86::parse_many_avx2(src, f),
+        #[cfg(target_arch = "aarch64")]
+        Self::Neon => neon::parse_many(src, f),
+      }
+    }
+  }
+
+  fn format_many<const N: usize, const L: usize, F>(self, len: usize, get: F, out: &mut [[u8; L]])
+  where
+    F: Fn(usize) -> [u8; N],
+  {
+    // SAFETY: every backend but `Scalar` is only selected if the CPU
+    // supports its instruction set.
+    unsafe {
+      match self {
+        Self::Scalar => format_loop::<Scalar, N, L, F>(len, get, out),
+        #[cfg(target_arch = "x86_64")]
+        Self::Sse41 => x86::format_many_sse41(len, get, out),
+        #[cfg(target_arch = "x86_64")]
+        Self::Avx2 => x86::format_many_avx2(len, get, out),
+        #[cfg(target_arch = "aarch64")]
+        Self::Neon => neon::format_many(len, get, out),
+      }
+    }
+  }
+}
+
+/// Parses and formats the colon- or hyphen-separated form of 6-octet
+/// addresses, one or two at a time.
+///
+/// The methods are `unsafe` because SIMD kernels may only run on CPUs that
+/// support their instruction set. They are `#[inline(always)]` and get
+/// inlined into a `#[target_feature]` entry point, see `parse_loop`.
+trait Kernel {
+  /// Returns `None` if `src` is not a valid address.
+  unsafe fn parse(src: &[u8; COLON6_LEN]) -> Option<[u8; 6]>;
+
+  #[inline(always)]
+  unsafe fn parse2(
+    a: &[u8; COLON6_LEN],
+    b: &[u8; COLON6_LEN],
+  ) -> (Option<[u8; 6]>, Option<[u8; 6]>) {
+    (Self::parse(a), Self::parse(b))
+  }
+
+  unsafe fn format(octets: &[u8; 6]) -> [u8; COLON6_LEN];
+
+  #[inline(always)]
+  unsafe fn format2(a: &[u8; 6], b: &[u8; 6]) -> ([u8; COLON6_LEN], [u8; COLON6_LEN]) {
+    (Self::format(a), Self::format(b))
+  }
+}
+
+struct Scalar;
+
+impl Kernel for Scalar {
+  #[inline(always)]
+  unsafe fn parse(src: &[u8; COLON6_LEN]) -> Option<[u8; 6]> {
+    parse::<6>(src).ok()
+  }
+
+  #[inline(always)]
+  unsafe fn format(octets: &[u8; 6]) -> [u8; COLON6_LEN] {
+    format_colon_scalar(octets)
+  }
+}
+
+/// The batch parsing loop. Pairs of 17-byte inputs go through `K`;
+/// everything else, including whatever `K` rejects, goes through the
+/// scalar parser, which also produces the error.
+///
+/// ## Safety
+///
+/// The CPU must support the instruction set of `K`.
+#[inline(always)]
+unsafe fn parse_loop<K: Kernel, const N: usize, F>(
+  src: &[&str],
+  mut f: F,
+) -> Result<(), BatchParseError>
+where
+  F: FnMut(usize, [u8; N]),
+{
+  let mut i = 0;
+  while i < src.len() {
+    if N == 6 {
+      if let Some(s) = src.get(i + 1) {
+        if let (Ok(a), Ok(b)) = (src[i].as_bytes().try_into(), s.as_bytes().try_into()) {
+          if let (Some(a), Some(b)) = K::parse2(a, b) {
+            f(i, widen(a));
+            f(i + 1, widen(b));
+            i += 2;
+            continue;
+          }
+        }
+      }
+
+      if let Ok(s) = src[i].as_bytes().try_into() {
+        if let Some(addr) = K::parse(s) {
+          f(i, widen(addr));
+          i += 1;
+          continue;
+        }
+      }
+    }
+
+    match parse::<N>(src[i].as_bytes()) {
+      Ok(addr) => f(i, addr),
+      Err(err) => return Err(BatchParseError::new(i, err.into())),
+    }
+    i += 1;
+  }
+  Ok(())
+}
+
+/// The batch formatting loop, `L` must be `N * 3 - 1`.
+///
+/// ## Safety
+///
+/// The CPU must support the instruction set of `K`.
+#[inline(always)]
+unsafe fn format_loop<K: Kernel, const N: usize, const L: usize, F>(
+  len: usize,
+  get: F,
+  out: &mut [[u8; L]],
+) where
+  F: Fn(usize) -> [u8; N],
+{
+  let out = &mut out[..len];
+  if N != 6 {
+    for (i, dst) in out.iter_mut().enumerate() {
+      *dst = format_colon_scalar::<N, L>(&get(i));
+    }
+    return;
+  }
+
+  let mut pairs = out.chunks_exact_mut(2);
+  let mut i = 0;
+  for pair in &mut pairs {
+    let (a, b) = K::format2(&narrow(get(i)), &narrow(get(i + 1)));
+    pair[0].copy_from_slice(&a);
+    pair[1].copy_from_slice(&b);
+    i += 2;
+  }
+  if let [last] = pairs.into_remainder() {
+    last.copy_from_slice(&K::format(&narrow(get(i))));
+  }
+}
+
+/// Converts between `[u8; 6]` and `[u8; N]` on the `N == 6` paths.
+#[inline(always)]
+fn widen<const N: usize>(src: [u8; 6]) -> [u8; N] {
+  let mut out = [0u8; N];
+  out.copy_from_slice(&src);
+  out
+}
+
+#[inline(always)]
+fn narrow<const N: usize>(src: [u8; N]) -> [u8; 6] {
+  let mut out = [0u8; 6];
+  out.copy_from_slice(&src);
+  out
+}
+
+/// Scalar colon-separated formatting, `L` must be `N * 3 - 1`.
+#[inline(always)]
+fn format_colon_scalar<const N: usize, const L: usize>(octets: &[u8; N]) -> [u8; L] {
+  let mut buf = [b':'; L];
+  for (i, &b) in octets.iter().enumerate() {
+    buf[i * 3] = HEX_DIGITS[(b >> 4) as usize];
+    buf[i * 3 + 1] = HEX_DIGITS[(b & 0xF) as usize];
+  }
+  buf
+}
+
+/// Returns the value of the hex digit `b`.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+#[inline(always)]
+fn hex_val(b: u8) -> Option<u8> {
+  match crate::__private::HEX_VAL[b as usize] {
+    0xFF => None,
+    v => Some(v),
+  }
+}
+
+/// Returns the separator of a colon- or hyphen-separated address.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+#[inline(always)]
+fn separator(src: &[u8; COLON6_LEN]) -> Option<u8> {
+  match src[2] {
+    sep @ (b':' | b'-') => Some(sep),
+    _ => None,
+  }
+}
+
+/// Bit `i` is set for every separator position `i` of the first 16 bytes of
+/// a colon-separated 6-octet address.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+const SEP_POSITIONS: u32 = (1 << 2) | (1 << 5) | (1 << 8) | (1 << 11) | (1 << 14);
+
+/// Bit `i` is set for every hex digit position `i` of the first 16 bytes.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+const DIGIT_POSITIONS: u32 = 0xFFFF & !SEP_POSITIONS;
+
+/// Byte indices of the high and low nibble of every octet in the first 16
+/// bytes; `-1` selects zero. The low nibble of the last octet (byte 16) is
+/// handled separately.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+const HI_NIBBLES: [i8; 16] = [0, 3, 6, 9, 12, 15, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1];
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+const LO_NIBBLES: [i8; 16] = [1, 4, 7, 10, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1];
+
+/// Index of the octet printed at every position of the first 16 bytes of
+/// the colon-separated form; separators select zero.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+const SPREAD: [i8; 16] = [0, 0, -1, 1, 1, -1, 2, 2, -1, 3, 3, -1, 4, 4, -1, 5];
+
+/// `-1` at every position of the first 16 bytes that holds a high nibble.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+const HI_POSITIONS: [i8; 16] = [-1, 0, 0, -1, 0, 0, -1, 0, 0, -1, 0, 0, -1, 0, 0, -1];
+
+/// `-1` at every separator position of the first 16 bytes.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+const SEP_BYTES: [i8; 16] = [0, 0, -1, 0, 0, -1, 0, 0, -1, 0, 0, -1, 0, 0, -1, 0];
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+  use core::arch::x86_64::*;
+
+  use super::*;
+
+  pub(super) struct Sse41;
+
+  pub(super) struct Avx2;
+
+  #[target_feature(enable = "sse4.1")]
+  pub(super) unsafe fn parse_many_sse41<const N: usize, F>(
+    src: &[&str],
+    f: F,
+  ) -> Result<(), BatchParseError>
+  where
+    F: FnMut(usize, [u8; N]),
+  {
+    parse_loop::<Sse41, N, F>(src, f)
+  }
+
+  #[target_feature(enable = "avx2,sse4.1")]
+  pub(super) unsafe fn parse_many_avx2<const N: usize, F>(
+    src: &[&str],
+    f: F,
+  ) -> Result<(), BatchParseError>
+  where
+    F: FnMut(usize, [u8; N]),
+  {
+    parse_loop::<Avx2, N, F>(src, f)
+  }
+
+  #[target_feature(enable = "sse4.1")]
+  pub(super) unsafe fn format_many_sse41<const N: usize, const L: usize, F>(
+    len: usize,
+    get: F,
+    out: &mut [[u8; L]],
+  ) where
+    F: Fn(usize) -> [u8; N],
+  {
+    format_loop::<Sse41, N, L, F>(len, get, out)
+  }
+
+  #[target_feature(enable = "avx2,sse4.1")]
+  pub(super) unsafe fn format_many_avx2<const N: usize, const L: usize, F>(
+    len: usize,
+    get: F,
+    out: &mut [[u8; L]],
+  ) where
+    F: Fn(usize) -> [u8; N],
+  {
+    format_loop::<Avx2, N, L, F>(len, get, out)
+  }
+
+  #[inline(always)]
+  unsafe fn load(bytes: &[i8; 16]) -> __m128i {
+    _mm_loadu_si128(bytes.as_ptr() as *const __m128i)
+  }
+
+  /// Loads `bytes` into both 128-bit lanes.
+  #[inline(always)]
+  unsafe fn both(bytes: &[i8; 16]) -> __m256i {
+    _mm256_broadcastsi128_si256(load(bytes))
+  }
+
+  #[inline(always)]
+  unsafe fn load6(octets: &[u8; 6]) -> __m128i {
+    let mut buf = [0u8; 16];
+    buf[..6].copy_from_slice(octets);
+    _mm_loadu_si128(buf.as_ptr() as *const __m128i)
+  }
+
+  /// Checks the separators and digits of the first 16 bytes of `src`, given
+  /// as bit masks, and assembles the octets from their nibble values.
+  #[inline(always)]
+  unsafe fn finish_parse(
+    src: &[u8; COLON6_LEN],
+    value: __m128i,
+    valid: u32,
+    seps: u32,
+  ) -> Option<[u8; 6]> {
+    let last = hex_val(src[16])?;
+    if valid & DIGIT_POSITIONS != DIGIT_POSITIONS || seps & SEP_POSITIONS != SEP_POSITIONS {
+      return None;
+    }
+
+    let hi = _mm_shuffle_epi8(value, load(&HI_NIBBLES));
+    let lo = _mm_shuffle_epi8(value, load(&LO_NIBBLES));
+    // Nibbles are at most 0x0f, so the 16-bit shift never crosses bytes.
+    let octets = _mm_or_si128(_mm_slli_epi16(hi, 4), lo);
+    let mut buf = [0u8; 16];
+    _mm_storeu_si128(buf.as_mut_ptr() as *mut __m128i, octets);
+    Some([buf[0], buf[1], buf[2], buf[3], buf[4], buf[5] | last])
+  }
+
+  #[inline(always)]
+  fn store17(v: __m128i, octets: &[u8; 6]) -> [u8; COLON6_LEN] {
+    let mut out = [0u8; COLON6_LEN];
+    // SAFETY: `out` has room for 16 bytes.
+    unsafe { _mm_storeu_si128(out.as_mut_ptr() as *mut __m128i, v) };
+    out[16] = HEX_DIGITS[(octets[5] & 0xF) as usize];
+    out
+  }
+
+  impl Kernel for Sse41 {
+    #[inline(always)]
+    unsafe fn parse(src: &[u8; COLON6_LEN]) -> Option<[u8; 6]> {
+      let sep = separator(src)?;
+      let v = _mm_loadu_si128(src.as_ptr() as *const __m128i);
+
+      let d = _mm_sub_epi8(v, _mm_set1_epi8(b'0' as i8));
+      let is_d = _mm_cmpeq_epi8(_mm_min_epu8(d, _mm_set1_epi8(9)), d);
+      let a = _mm_sub_epi8(
+        _mm_or_si128(v, _mm_set1_epi8(0x20)),
+        _mm_set1_epi8(b'a' as i8),
+      );
+      let is_a = _mm_cmpeq_epi8(_mm_min_epu8(a, _mm_set1_epi8(5)), a);
+      let a = _mm_add_epi8(a, _mm_set1_epi8(10));
+      let value = _mm_or_si128(_mm_and_si128(is_d, d), _mm_and_si128(is_a, a));
+
+      let valid = _mm_movemask_epi8(_mm_or_si128(is_d, is_a)) as u32;
+      let seps = _mm_movemask_epi8(_mm_cmpeq_epi8(v, _mm_set1_epi8(sep as i8))) as u32;
+      finish_parse(src, value, valid, seps)
+    }
+
+    #[inline(always)]
+    unsafe fn format(octets: &[u8; 6]) -> [u8; COLON6_LEN] {
+      let spread = _mm_shuffle_epi8(load6(octets), load(&SPREAD));
+      let lo = _mm_and_si128(spread, _mm_set1_epi8(0x0F));
+      let hi = _mm_and_si128(_mm_srli_epi16(spread, 4), _mm_set1_epi8(0x0F));
+      let nibbles = _mm_blendv_epi8(lo, hi, load(&HI_POSITIONS));
+      let digits = _mm_shuffle_epi8(
+        _mm_loadu_si128(HEX_DIGITS.as_ptr() as *const __m128i),
+        nibbles,
+      );
+      let out = _mm_blendv_epi8(digits, _mm_set1_epi8(b':' as i8), load(&SEP_BYTES));
+      store17(out, octets)
+    }
+  }
+
+  /// Handles two addresses at once, one per 128-bit lane.
+  impl Kernel for Avx2 {
+    #[inline(always)]
+    unsafe fn parse(src: &[u8; COLON6_LEN]) -> Option<[u8; 6]> {
+      Sse41::parse(src)
+    }
+
+    #[inline(always)]
+    unsafe fn parse2(
+      a: &[u8; COLON6_LEN],
+      b: &[u8; COLON6_LEN],
+    ) -> (Option<[u8; 6]>, Option<[u8; 6]>) {
+      let (sep_a, sep_b) = match (separator(a), separator(b)) {
+        (Some(sep_a), Some(sep_b)) => (sep_a, sep_b),
+        _ => return (None, None),
+      };
+
+      let va = _mm_loadu_si128(a.as_ptr() as *const __m128i);
+      let vb = _mm_loadu_si128(b.as_ptr() as *const __m128i);
+      let v = _mm256_inserti128_si256::<1>(_mm256_castsi128_si256(va), vb);
+      let sep = _mm256_inserti128_si256::<1>(
+        _mm256_castsi128_si256(_mm_set1_epi8(sep_a as i8)),
+        _mm_set1_epi8(sep_b as i8),
+      );
+
+      let d = _mm256_sub_epi8(v, _mm256_set1_epi8(b'0' as i8));
+      let is_d = _mm256_cmpeq_epi8(_mm256_min_epu8(d, _mm256_set1_epi8(9)), d);
+      let x = _mm256_sub_epi8(
+        _mm256_or_si256(v, _mm256_set1_epi8(0x20)),
+        _mm256_set1_epi8(b'a' as i8),
+      );
+      let is_a = _mm256_cmpeq_epi8(_mm256_min_epu8(x, _mm256_set1_epi8(5)), x);
+      let x = _mm256_add_epi8(x, _mm256_set1_epi8(10));
+      let value = _mm256_or_si256(_mm256_and_si256(is_d, d), _mm256_and_si256(is_a, x));
+      let valid = _mm256_movemask_epi8(_mm256_or_si256(is_d, is_a)) as u32;
+      let seps = _mm256_movemask_epi8(_mm256_cmpeq_epi8(v, sep)) as u32;
+
+      (
+        finish_parse(
+          a,
+          _mm256_castsi256_si128(value),
+          valid & 0xFFFF,
+          seps & 0xFFFF,
+        ),
+        finish_parse(
+          b,
+          _mm256_extracti128_si256::<1>(value),
+          valid >> 16,
+          seps >> 16,
+        ),
+      )
+    }
+
+    #[inline(always)]
+    unsafe fn format(octets: &[u8; 6]) -> [u8; COLON6_LEN] {
+      Sse41::format(octets)
+    }
+
+    #[inline(always)]
+    unsafe fn format2(a: &[u8; 6], b: &[u8; 6]) -> ([u8; COLON6_LEN], [u8; COLON6_LEN]) {
+      let v = _mm256_inserti128_si256::<1>(_mm256_castsi128_si256(load6(a)), load6(b));
+
+      let spread = _mm256_shuffle_epi8(v, both(&SPREAD));
+      let lo = _mm256_and_si256(spread, _mm256_set1_epi8(0x0F));
+      let hi = _mm256_and_si256(_mm256_srli_epi16(spread, 4), _mm256_set1_epi8(0x0F));
+      let nibbles = _mm256_blendv_epi8(lo, hi, both(&HI_POSITIONS));
+      let digits = _mm256_shuffle_epi8(
+        _mm256_broadcastsi128_si256(_mm_loadu_si128(HEX_DIGITS.as_ptr() as *const __m128i)),
+        nibbles,
+      );
+      let out = _mm256_blendv_epi8(digits, _mm256_set1_epi8(b':' as i8), both(&SEP_BYTES));
+
+      (
+        store17(_mm256_castsi256_si128(out), a),
+        store17(_mm256_extracti128_si256::<1>(out), b),
+      )
+    }
+  }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod neon {
+  use core::arch::aarch64::*;
+
+  use super::*;
+
+  pub(super) struct Neon;
+
+  #[target_feature(enable = "neon")]
+  pub(super) unsafe fn parse_many<const N: usize, F>(
+    src: &[&str],
+    f: F,
+  ) -> Result<(), BatchParseError>
+  where
+    F: FnMut(usize, [u8; N]),
+  {
+    parse_loop::<Neon, N, F>(src, f)
+  }
+
+  #[target_feature(enable = "neon")]
+  pub(super) unsafe fn format_many<const N: usize, const L: usize, F>(
+    len: usize,
+    get: F,
+    out: &mut [[u8; L]],
+  ) where
+    F: Fn(usize) -> [u8; N],
+  {
+    format_loop::<Neon, N, L, F>(len, get, out)
+  }
+
+  #[inline(always)]
+  unsafe fn load(bytes: &[i8; 16]) -> uint8x16_t {
+    vld1q_u8(bytes.as_ptr() as *const u8)
+  }
+
+  /// Expands the low 16 bits of `mask` into a byte mask.
+  const fn mask_bytes(mask: u32) -> [i8; 16] {
+    let mut out = [0i8; 16];
+    let mut i = 0;
+    while i < 16 {
+      if mask & (1 << i) != 0 {
+        out[i] = -1;
+      }
+      i += 1;
+    }
+    out
+  }
+
+  const DIGIT_BYTES: [i8; 16] = mask_bytes(DIGIT_POSITIONS);
+  const SEP_POSITION_BYTES: [i8; 16] = mask_bytes(SEP_POSITIONS);
+
+  impl Kernel for Neon {
+    #[inline(always)]
+    unsafe fn parse(src: &[u8; COLON6_LEN]) -> Option<[u8; 6]> {
+      let sep = separator(src)?;
+      let last = hex_val(src[16])?;
+
+      let v = vld1q_u8(src.as_ptr());
+      let d = vsubq_u8(v, vdupq_n_u8(b'0'));
+      let is_d = vcleq_u8(d, vdupq_n_u8(9));
+      let a = vsubq_u8(vorrq_u8(v, vdupq_n_u8(0x20)), vdupq_n_u8(b'a'));
+      let is_a = vcleq_u8(a, vdupq_n_u8(5));
+      let a = vaddq_u8(a, vdupq_n_u8(10));
+      let value = vorrq_u8(vandq_u8(is_d, d), vandq_u8(is_a, a));
+
+      // Every digit position must hold a digit and every separator position
+      // the separator.
+      let ok = vorrq_u8(
+        vandq_u8(vorrq_u8(is_d, is_a), load(&DIGIT_BYTES)),
+        vandq_u8(vceqq_u8(v, vdupq_n_u8(sep)), load(&SEP_POSITION_BYTES)),
+      );
+      if vminvq_u8(ok) != 0xFF {
+        return None;
+      }
+
+      let hi = vqtbl1q_u8(value, load(&HI_NIBBLES));
+      let lo = vqtbl1q_u8(value, load(&LO_NIBBLES));
+      let octets = vorrq_u8(vshlq_n_u8::<4>(hi), lo);
+      let mut buf = [0u8; 16];
+      vst1q_u8(buf.as_mut_ptr(), octets);
+      Some([buf[0], buf[1], buf[2], buf[3], buf[4], buf[5] | last])
+    }
+
+    #[inline(always)]
+    unsafe fn format(octets: &[u8; 6]) -> [u8; COLON6_LEN] {
+      let mut buf = [0u8; 16];
+      buf[..6].copy_from_slice(octets);
+      let v = vld1q_u8(buf.as_ptr());
+
+      let spread = vqtbl1q_u8(v, load(&SPREAD));
+      let lo = vandq_u8(spread, vdupq_n_u8(0x0F));
+      let hi = vshrq_n_u8::<4>(spread);
+      let nibbles = vbslq_u8(load(&HI_POSITIONS), hi, lo);
+      let digits = vqtbl1q_u8(vld1q_u8(HEX_DIGITS.as_ptr()), nibbles);
+      let out = vbslq_u8(load(&SEP_BYTES), vdupq_n_u8(b':'), digits);
+
+      let mut res = [0u8; COLON6_LEN];
+      vst1q_u8(res.as_mut_ptr(), out);
+      res[16] = HEX_DIGITS[(octets[5] & 0xF) as usize];
+      res
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{Eui64Addr, MacAddr};
+
+  use std::{string::String, vec, vec::Vec};
+
+  /// A small xorshift generator, so the tests need no extra dependency.
+  struct Rng(u64);
+
+  impl Rng {
+    fn next(&mut self) -> u64 {
+      self.0 ^= self.0 << 13;
+      self.0 ^= self.0 >> 7;
+      self.0 ^= self.0 << 17;
+      self.0
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+      items[(self.next() % items.len() as u64) as usize]
+    }
+  }
+
+  /// Colon- or hyphen-separated candidates of 17 bytes, most of them valid
+  /// and the rest with one corrupted byte.
+  fn candidates(rng: &mut Rng, count: usize) -> Vec<String> {
+    const DIGITS: &[u8] = b"0123456789abcdefABCDEF";
+    const JUNK: &[u8] = b":-.gG/@`xX \x00\xff9aF";
+    (0..count)
+      .map(|_| {
+        let sep = rng.pick(b":-");
+        let mut s: Vec<u8> = (0..COLON6_LEN)
+          .map(|i| if i % 3 == 2 { sep } else { rng.pick(DIGITS) })
+          .collect();
+        if rng.next() % 3 == 0 {
+          let at = (rng.next() % COLON6_LEN as u64) as usize;
+          s[at] = rng.pick(JUNK);
+        }
+        String::from_utf8_lossy(&s).into_owned()
+      })
+      .collect()
+  }
+
+  /// Every available kernel must agree with `parse`.
+  #[test]
+  fn parse_kernels_match_scalar() {
+    let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+    let inputs = candidates(&mut rng, 20_000);
+    let inputs: Vec<&[u8; COLON6_LEN]> = inputs
+      .iter()
+      .filter_map(|s| s.as_bytes().try_into().ok())
+      .collect();
+    for backend in Backend::available() {
+      for pair in inputs.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let (ea, eb) = (parse::<6>(a).ok(), parse::<6>(b).ok());
+        let [single, x, y] = backend.parse_kernels(a, b);
+        assert_eq!(single, ea, "{backend:?}: {a:?}");
+        // The pair kernels may give up on both if either is invalid.
+        if ea.is_some() && eb.is_some() {
+          assert_eq!((x, y), (ea, eb), "{backend:?}: {a:?} {b:?}");
+        } else {
+          assert!(x.is_none() || x == ea, "{backend:?}: {a:?}");
+          assert!(y.is_none() || y == eb, "{backend:?}: {b:?}");
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn parse_many_matches_scalar() {
+    let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+    let mut inputs = candidates(&mut rng, 4_000);
+    inputs.extend(["0000.5e00.5301", "0x00005e005301", "00005E005301"].map(String::from));
+    let inputs: Vec<&str> = inputs.iter().map(String::as_str).collect();
+
+    for backend in Backend::available() {
+      let mut start = 0;
+      while start < inputs.len() {
+        let src = &inputs[start..];
+        let mut out = vec![[0u8; 6]; src.len()];
+        let res = backend.parse_many(src, |i, addr| out[i] = addr);
+
+        // Everything before the first error (or everything) matches `parse`.
+        let end = match res {
+          Ok(()) => src.len(),
+          Err(err) => {
+            let expected = parse::<6>(src[err.index()].as_bytes()).unwrap_err();
+            assert_eq!(err.error(), expected.into(), "{backend:?}");
+            err.index()
+          }
+        };
+        for (s, addr) in src.iter().zip(&out).take(end) {
+          assert_eq!(Ok(*addr), parse::<6>(s.as_bytes()), "{backend:?}");
+        }
+        start += end + 1;
+      }
+    }
+  }
+
+  #[test]
+  fn format_kernels_match_scalar() {
+    let mut rng = Rng(0xD1B5_4A32_D192_ED03);
+    let addrs: Vec<[u8; 6]> = (0..4_001)
+      .map(|_| {
+        let b = rng.next().to_le_bytes();
+        [b[0], b[1], b[2], b[3], b[4], b[5]]
+      })
+      .chain([[0x00; 6], [0xff; 6], [0x0f, 0xf0, 0xa5, 0x5a, 0x90, 0x09]])
+      .collect();
+
+    for backend in Backend::available() {
+      for pair in addrs.windows(2) {
+        let [single, x, y] = backend.format_kernels(&pair[0], &pair[1]);
+        let a = MacAddr::from_raw(pair[0]).to_colon_separated_array();
+        let b = MacAddr::from_raw(pair[1]).to_colon_separated_array();
+        assert_eq!([single, x, y], [a, a, b], "{backend:?}");
+      }
+
+      let mut out = vec![[0u8; COLON6_LEN]; addrs.len()];
+      backend.format_many(addrs.len(), |i| addrs[i], &mut out);
+      for (addr, s) in addrs.iter().zip(&out) {
+        assert_eq!(
+          s,
+          &MacAddr::from_raw(*addr).to_colon_separated_array(),
+          "{backend:?}"
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn other_sizes() {
+    let src = ["02:00:5e:10:00:00:00:01", "0200.5e10.0000.0002"];
+    let mut out = [Eui64Addr::new(); 2];
+    Eui64Addr::parse_many(&src, &mut out).unwrap();
+    assert_eq!(out[1].octets()[7], 0x02);
+
+    let mut text = [[0u8; 23]; 2];
+    Eui64Addr::format_many(&out, &mut text);
+    assert_eq!(&text[0], b"02:00:5e:10:00:00:00:01");
+    assert_eq!(&text[1], b"02:00:5e:10:00:00:00:02");
+
+    let err = Eui64Addr::parse_many(&["02:00:5e:10:00:00:00:0g"], &mut out[..1]).unwrap_err();
+    assert_eq!(err.index(), 0);
+    assert_eq!(err.error().size(), 8);
+    assert_eq!(err.error().offset(), 22);
+  }
+
+  #[test]
+  #[should_panic]
+  fn length_mismatch() {
+    let _ = parse_many::<6>(&["00:00:5e:00:53:01"], &mut []);
+  }
+
+  #[test]
+  #[should_panic(expected = "`L` must be `N * 3 - 1`")]
+  fn zero_octets() {
+    format_many::<0, 0>(&[], &mut []);
+  }
+}
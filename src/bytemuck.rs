@@ -0,0 +1,17 @@
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __addr_ty_bytemuck {
+  (
+    $name:ident[$n:expr]
+  ) => {
+    const _: () = {
+      // SAFETY: the address is a `#[repr(transparent)]` wrapper around
+      // `[u8; $n]`, for which the all-zero bit pattern is valid.
+      unsafe impl $crate::__private::bytemuck::Zeroable for $name {}
+
+      // SAFETY: as above, and `[u8; $n]` is `Pod`: every bit pattern is
+      // valid and it has no padding.
+      unsafe impl $crate::__private::bytemuck::Pod for $name {}
+    };
+  };
+}
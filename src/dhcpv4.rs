@@ -0,0 +1,616 @@
+//! Client hardware addresses in DHCPv4 ([RFC 2131]) messages and the Client
+//! Identifier option ([RFC 2132], option 61).
+//!
+//! The BOOTP header carries the client address in the 16-byte `chaddr`
+//! field, described by `htype` and `hlen`. IP over InfiniBand addresses do
+//! not fit, so such clients send `htype` 32, `hlen` 0 and a zeroed
+//! `chaddr` ([RFC 4390]) and are identified by an [RFC 4361] client
+//! identifier: type 255, an IAID and a [`Duid`].
+//!
+//! [RFC 2131]: https://www.rfc-editor.org/rfc/rfc2131#section-2
+//! [RFC 2132]: https://www.rfc-editor.org/rfc/rfc2132#section-9.14
+//! [RFC 4390]: https://www.rfc-editor.org/rfc/rfc4390#section-2.1
+//! [RFC 4361]: https://www.rfc-editor.org/rfc/rfc4361#section-6.1
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hardware_address::{
+//!   dhcpv4::{Chaddr, ClientId},
+//!   MacAddr,
+//! };
+//!
+//! let mac = MacAddr::from_raw([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+//! let chaddr = Chaddr::from(mac);
+//! assert_eq!((chaddr.htype(), chaddr.hlen()), (1, 6));
+//! assert_eq!(chaddr.mac_addr(), Some(mac));
+//!
+//! let id: ClientId = "01:00:11:22:33:44:55".parse().unwrap();
+//! assert_eq!(id, ClientId::mac(mac));
+//! assert_eq!(id.mac_addr(), Some(mac));
+//! assert_eq!(id.to_string(), "01:00:11:22:33:44:55");
+//! ```
+
+use core::{fmt, str::FromStr};
+
+use crate::{
+  arp::HardwareType,
+  duid::{Duid, DuidError},
+  hex::{decode_hex, write_hex, HexError},
+  AnyHardwareAddr, Eui64Addr, InfiniBandAddr, MacAddr,
+};
+
+/// An error returned while decoding, encoding or parsing DHCPv4 hardware
+/// address fields or client identifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+pub enum Dhcpv4Error {
+  /// The input or buffer is too short.
+  #[error("buffer too short: expected at least {expected} bytes, but got {actual} bytes")]
+  TooShort {
+    /// The number of bytes needed.
+    expected: usize,
+    /// The actual number of bytes.
+    actual: usize,
+  },
+  /// The `hlen` field is larger than the 16-byte `chaddr` field.
+  #[error("hardware address length {0} is longer than 16 bytes")]
+  InvalidHardwareLength(u8),
+  /// The client identifier is longer than 255 bytes.
+  #[error("client identifier of {0} bytes is longer than 255 bytes")]
+  TooLong(usize),
+  /// The DUID of an RFC 4361 client identifier is malformed.
+  #[error("invalid DUID: {0}")]
+  Duid(#[from] DuidError),
+  /// The text is not colon-separated or bare hex octets.
+  #[error("invalid client identifier text at offset {0}")]
+  InvalidText(usize),
+}
+
+/// The offset of `htype` in a BOOTP message; `hlen` follows it.
+const HTYPE_OFFSET: usize = 1;
+
+/// The offset of `chaddr` in a BOOTP message.
+const CHADDR_OFFSET: usize = 28;
+
+/// The `htype`, `hlen` and `chaddr` fields of a DHCPv4 message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Chaddr {
+  htype: u8,
+  hlen: u8,
+  chaddr: [u8; 16],
+}
+
+impl Chaddr {
+  /// The size of the `chaddr` field.
+  pub const SIZE: usize = 16;
+
+  /// Creates the fields from their raw values.
+  #[inline]
+  pub const fn new(htype: u8, hlen: u8, chaddr: [u8; 16]) -> Self {
+    Self {
+      htype,
+      hlen,
+      chaddr,
+    }
+  }
+
+  /// Creates the fields for a client with address `addr`.
+  ///
+  /// MAC and EUI-64 addresses are stored in `chaddr`; an IP over InfiniBand
+  /// address gives `hlen` 0 and a zeroed `chaddr`, as RFC 4390 requires.
+  #[inline]
+  pub const fn from_addr(addr: &AnyHardwareAddr) -> Self {
+    let htype = HardwareType::of(addr).to_u16() as u8;
+    let mut chaddr = [0; 16];
+    let hlen = match addr {
+      AnyHardwareAddr::Mac(addr) => {
+        let mut i = 0;
+        while i < MacAddr::SIZE {
+          chaddr[i] = addr.0[i];
+          i += 1;
+        }
+        MacAddr::SIZE
+      }
+      AnyHardwareAddr::Eui64(addr) => {
+        let mut i = 0;
+        while i < Eui64Addr::SIZE {
+          chaddr[i] = addr.0[i];
+          i += 1;
+        }
+        Eui64Addr::SIZE
+      }
+      AnyHardwareAddr::InfiniBand(_) => 0,
+    };
+    Self {
+      htype,
+      hlen: hlen as u8,
+      chaddr,
+    }
+  }
+
+  /// Returns the `htype` field.
+  #[inline]
+  pub const fn htype(&self) -> u8 {
+    self.htype
+  }
+
+  /// Returns the `htype` field as a hardware type.
+  #[inline]
+  pub const fn hardware_type(&self) -> HardwareType {
+    HardwareType::from_u16(self.htype as u16)
+  }
+
+  /// Returns the `hlen` field.
+  #[inline]
+  pub const fn hlen(&self) -> u8 {
+    self.hlen
+  }
+
+  /// Returns the `chaddr` field, including the padding after the address.
+  #[inline]
+  pub const fn chaddr(&self) -> &[u8; 16] {
+    &self.chaddr
+  }
+
+  /// Returns the client address, if `hlen` is the size of a MAC or EUI-64
+  /// address.
+  ///
+  /// IP over InfiniBand clients do not send their address in `chaddr`;
+  /// see [`ClientId::hardware_addr`].
+  #[inline]
+  pub fn hardware_addr(&self) -> Option<AnyHardwareAddr> {
+    match self.hlen as usize {
+      MacAddr::SIZE | Eui64Addr::SIZE => {
+        AnyHardwareAddr::from_bytes(&self.chaddr[..self.hlen as usize])
+      }
+      _ => None,
+    }
+  }
+
+  /// Returns the client address if it is a MAC address.
+  #[inline]
+  pub fn mac_addr(&self) -> Option<MacAddr> {
+    match self.hardware_addr() {
+      Some(AnyHardwareAddr::Mac(addr)) => Some(addr),
+      _ => None,
+    }
+  }
+
+  /// Reads the fields from a DHCPv4 message.
+  pub fn decode(message: &[u8]) -> Result<Self, Dhcpv4Error> {
+    let end = CHADDR_OFFSET + Self::SIZE;
+    if message.len() < end {
+      return Err(Dhcpv4Error::TooShort {
+        expected: end,
+        actual: message.len(),
+      });
+    }
+    let hlen = message[HTYPE_OFFSET + 1];
+    if hlen as usize > Self::SIZE {
+      return Err(Dhcpv4Error::InvalidHardwareLength(hlen));
+    }
+    let mut chaddr = [0; 16];
+    chaddr.copy_from_slice(&message[CHADDR_OFFSET..end]);
+    Ok(Self::new(message[HTYPE_OFFSET], hlen, chaddr))
+  }
+
+  /// Writes the fields into a DHCPv4 message, leaving the other fields
+  /// untouched.
+  pub fn encode(&self, message: &mut [u8]) -> Result<(), Dhcpv4Error> {
+    let end = CHADDR_OFFSET + Self::SIZE;
+    if message.len() < end {
+      return Err(Dhcpv4Error::TooShort {
+        expected: end,
+        actual: message.len(),
+      });
+    }
+    message[HTYPE_OFFSET] = self.htype;
+    message[HTYPE_OFFSET + 1] = self.hlen;
+    message[CHADDR_OFFSET..end].copy_from_slice(&self.chaddr);
+    Ok(())
+  }
+}
+
+impl From<AnyHardwareAddr> for Chaddr {
+  #[inline]
+  fn from(addr: AnyHardwareAddr) -> Self {
+    Self::from_addr(&addr)
+  }
+}
+
+impl From<MacAddr> for Chaddr {
+  #[inline]
+  fn from(addr: MacAddr) -> Self {
+    Self::from_addr(&AnyHardwareAddr::Mac(addr))
+  }
+}
+
+impl From<Eui64Addr> for Chaddr {
+  #[inline]
+  fn from(addr: Eui64Addr) -> Self {
+    Self::from_addr(&AnyHardwareAddr::Eui64(addr))
+  }
+}
+
+impl From<InfiniBandAddr> for Chaddr {
+  #[inline]
+  fn from(addr: InfiniBandAddr) -> Self {
+    Self::from_addr(&AnyHardwareAddr::InfiniBand(addr))
+  }
+}
+
+/// The longest client identifier, its type and 254 bytes.
+const MAX_LEN: usize = 255;
+
+/// The contents of a client identifier of another type, stored inline, at
+/// most 254 bytes.
+#[derive(Clone, Copy)]
+pub struct ClientIdData {
+  buf: [u8; MAX_LEN - 1],
+  len: u8,
+}
+
+impl ClientIdData {
+  /// Copies `src`, returning `None` if it is longer than 254 bytes.
+  #[inline]
+  pub fn new(src: &[u8]) -> Option<Self> {
+    if src.len() > MAX_LEN - 1 {
+      return None;
+    }
+    let mut buf = [0; MAX_LEN - 1];
+    buf[..src.len()].copy_from_slice(src);
+    Some(Self {
+      buf,
+      len: src.len() as u8,
+    })
+  }
+
+  /// Returns the bytes.
+  #[inline]
+  pub fn as_bytes(&self) -> &[u8] {
+    &self.buf[..self.len as usize]
+  }
+}
+
+impl fmt::Debug for ClientIdData {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_tuple("ClientIdData")
+      .field(&self.as_bytes())
+      .finish()
+  }
+}
+
+impl PartialEq for ClientIdData {
+  #[inline]
+  fn eq(&self, other: &Self) -> bool {
+    self.as_bytes() == other.as_bytes()
+  }
+}
+
+impl Eq for ClientIdData {}
+
+impl core::hash::Hash for ClientIdData {
+  #[inline]
+  fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+    self.as_bytes().hash(state)
+  }
+}
+
+impl AsRef<[u8]> for ClientIdData {
+  #[inline]
+  fn as_ref(&self) -> &[u8] {
+    self.as_bytes()
+  }
+}
+
+/// The contents of a DHCPv4 Client Identifier option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClientId {
+  /// A hardware type followed by a hardware address, most often `01` and a
+  /// MAC address.
+  HardwareAddr {
+    /// The hardware type, as in `htype`.
+    htype: u8,
+    /// The hardware address.
+    addr: AnyHardwareAddr,
+  },
+  /// An RFC 4361 identifier: type 255, an IAID and a DUID.
+  Duid {
+    /// The identity association identifier of the interface.
+    iaid: u32,
+    /// The DUID of the client.
+    duid: Duid,
+  },
+  /// Any other identifier, such as type 0 followed by text.
+  Other {
+    /// The identifier type.
+    id_type: u8,
+    /// The contents after the type.
+    data: ClientIdData,
+  },
+}
+
+impl ClientId {
+  /// Creates the usual identifier of an Ethernet client, `01` followed by
+  /// its MAC address.
+  #[inline]
+  pub const fn mac(addr: MacAddr) -> Self {
+    Self::HardwareAddr {
+      htype: 1,
+      addr: AnyHardwareAddr::Mac(addr),
+    }
+  }
+
+  /// Creates an RFC 4361 identifier.
+  #[inline]
+  pub const fn from_duid(iaid: u32, duid: Duid) -> Self {
+    Self::Duid { iaid, duid }
+  }
+
+  /// Returns the identifier type.
+  #[inline]
+  pub const fn id_type(&self) -> u8 {
+    match self {
+      Self::HardwareAddr { htype, .. } => *htype,
+      Self::Duid { .. } => 255,
+      Self::Other { id_type, .. } => *id_type,
+    }
+  }
+
+  /// Returns the hardware address of the identifier, or the link-layer
+  /// address embedded in its DUID-LLT or DUID-LL.
+  #[inline]
+  pub const fn hardware_addr(&self) -> Option<AnyHardwareAddr> {
+    match self {
+      Self::HardwareAddr { addr, .. } => Some(*addr),
+      Self::Duid { duid, .. } => duid.hardware_addr(),
+      Self::Other { .. } => None,
+    }
+  }
+
+  /// Returns the hardware address of the identifier if it is a MAC
+  /// address.
+  #[inline]
+  pub const fn mac_addr(&self) -> Option<MacAddr> {
+    match self.hardware_addr() {
+      Some(AnyHardwareAddr::Mac(addr)) => Some(addr),
+      _ => None,
+    }
+  }
+
+  /// Returns the length of the encoded identifier in bytes.
+  #[inline]
+  pub fn encoded_len(&self) -> usize {
+    1 + match self {
+      Self::HardwareAddr { addr, .. } => addr.size(),
+      Self::Duid { duid, .. } => 4 + duid.encoded_len(),
+      Self::Other { data, .. } => data.as_bytes().len(),
+    }
+  }
+
+  /// Decodes the contents of a Client Identifier option, without its code
+  /// and length.
+  ///
+  /// Identifiers with a hardware type other than 0 and the size of a
+  /// supported address decode as [`HardwareAddr`](Self::HardwareAddr).
+  pub fn decode(src: &[u8]) -> Result<Self, Dhcpv4Error> {
+    if src.len() < 2 {
+      return Err(Dhcpv4Error::TooShort {
+        expected: 2,
+        actual: src.len(),
+      });
+    }
+    if src.len() > MAX_LEN {
+      return Err(Dhcpv4Error::TooLong(src.len()));
+    }
+    let (id_type, rest) = (src[0], &src[1..]);
+    match id_type {
+      255 => {
+        if rest.len() < 4 {
+          return Err(Dhcpv4Error::TooShort {
+            expected: 5,
+            actual: src.len(),
+          });
+        }
+        Ok(Self::Duid {
+          iaid: u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]),
+          duid: Duid::decode(&rest[4..])?,
+        })
+      }
+      0 => Ok(Self::other(id_type, rest)),
+      htype => match AnyHardwareAddr::from_bytes(rest) {
+        Some(addr) => Ok(Self::HardwareAddr { htype, addr }),
+        None => Ok(Self::other(id_type, rest)),
+      },
+    }
+  }
+
+  #[inline]
+  fn other(id_type: u8, data: &[u8]) -> Self {
+    Self::Other {
+      id_type,
+      data: ClientIdData::new(data).unwrap(),
+    }
+  }
+
+  /// Encodes the identifier to the start of `buf`, returning its length.
+  pub fn encode(&self, buf: &mut [u8]) -> Result<usize, Dhcpv4Error> {
+    let len = self.encoded_len();
+    if len > MAX_LEN {
+      return Err(Dhcpv4Error::TooLong(len));
+    }
+    if buf.len() < len {
+      return Err(Dhcpv4Error::TooShort {
+        expected: len,
+        actual: buf.len(),
+      });
+    }
+    buf[0] = self.id_type();
+    let rest = &mut buf[1..len];
+    match self {
+      Self::HardwareAddr { addr, .. } => rest.copy_from_slice(addr.as_bytes()),
+      Self::Duid { iaid, duid } => {
+        rest[..4].copy_from_slice(&iaid.to_be_bytes());
+        duid.encode(&mut rest[4..])?;
+      }
+      Self::Other { data, .. } => rest.copy_from_slice(data.as_bytes()),
+    }
+    Ok(len)
+  }
+}
+
+impl From<MacAddr> for ClientId {
+  #[inline]
+  fn from(addr: MacAddr) -> Self {
+    Self::mac(addr)
+  }
+}
+
+/// Parses colon-separated (`01:00:11:22:33:44:55`, as ISC dhcpd and Kea
+/// print them) or bare (`01001122334455`) hex octets.
+impl FromStr for ClientId {
+  type Err = Dhcpv4Error;
+
+  fn from_str(src: &str) -> Result<Self, Self::Err> {
+    let mut buf = [0u8; MAX_LEN];
+    match decode_hex(src, &mut buf) {
+      Ok(len) => Self::decode(&buf[..len]),
+      Err(HexError::InvalidText(at)) => Err(Dhcpv4Error::InvalidText(at)),
+      Err(HexError::TooLong(len)) => Err(Dhcpv4Error::TooLong(len)),
+    }
+  }
+}
+
+/// Formats the identifier as colon-separated lowercase hex octets.
+impl fmt::Display for ClientId {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut buf = [0u8; MAX_LEN];
+    match self.encode(&mut buf) {
+      Ok(len) => write_hex(&buf[..len], f),
+      Err(_) => Err(fmt::Error),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use std::string::ToString;
+
+  const MAC: MacAddr = MacAddr::from_raw([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+
+  #[test]
+  fn chaddr() {
+    let eui = Eui64Addr::from_raw([0x02, 0x00, 0x5e, 0x10, 0x00, 0x00, 0x00, 0x01]);
+    let ib = InfiniBandAddr::from_raw([0x11; 20]);
+    let cases: [(AnyHardwareAddr, u8, u8, bool); 3] = [
+      (MAC.into(), 1, 6, true),
+      (eui.into(), 27, 8, true),
+      (ib.into(), 32, 0, false),
+    ];
+    for (addr, htype, hlen, recoverable) in cases {
+      let fields = Chaddr::from(addr);
+      assert_eq!((fields.htype(), fields.hlen()), (htype, hlen));
+      assert_eq!(fields.hardware_type(), HardwareType::of(&addr));
+      assert_eq!(
+        &fields.chaddr()[..hlen as usize],
+        &addr.as_bytes()[..hlen as usize]
+      );
+      assert!(fields.chaddr()[hlen as usize..].iter().all(|&b| b == 0));
+      assert_eq!(fields.hardware_addr(), recoverable.then_some(addr));
+
+      let mut message = [0xffu8; 44];
+      fields.encode(&mut message).unwrap();
+      assert_eq!(message[0], 0xff);
+      assert_eq!(message[3..28], [0xff; 25]);
+      assert_eq!(Chaddr::decode(&message), Ok(fields));
+    }
+    assert_eq!(Chaddr::from(MAC).mac_addr(), Some(MAC));
+    assert_eq!(Chaddr::from(ib).mac_addr(), None);
+
+    let mut message = [0u8; 44];
+    message[2] = 17;
+    assert_eq!(
+      Chaddr::decode(&message),
+      Err(Dhcpv4Error::InvalidHardwareLength(17))
+    );
+    assert_eq!(
+      Chaddr::decode(&message[..43]),
+      Err(Dhcpv4Error::TooShort {
+        expected: 44,
+        actual: 43
+      })
+    );
+  }
+
+  #[test]
+  fn client_id() {
+    let id: ClientId = "01001122334455".parse().unwrap();
+    assert_eq!(id, ClientId::from(MAC));
+    assert_eq!(id.encoded_len(), 7);
+
+    // RFC 4361: IAID 1 and the DUID-LL of an InfiniBand port.
+    let ib = InfiniBandAddr::from_raw([0x11; 20]);
+    let id = ClientId::from_duid(1, Duid::ll(ib));
+    assert_eq!(id.id_type(), 255);
+    assert_eq!(id.hardware_addr(), Some(ib.into()));
+    assert_eq!(id.mac_addr(), None);
+    let text = id.to_string();
+    assert!(text.starts_with("ff:00:00:00:01:00:03:00:20:11:11"));
+    assert_eq!(text.parse(), Ok(id));
+
+    let id: ClientId = "00:68:6f:73:74".parse().unwrap();
+    assert_eq!(
+      id,
+      ClientId::Other {
+        id_type: 0,
+        data: ClientIdData::new(b"host").unwrap(),
+      }
+    );
+    assert_eq!(id.hardware_addr(), None);
+    assert_eq!(id.to_string(), "00:68:6f:73:74");
+
+    // An unusual length is kept as is.
+    let id: ClientId = "01:00:11:22".parse().unwrap();
+    assert_eq!(id.id_type(), 1);
+    assert_eq!(id.hardware_addr(), None);
+  }
+
+  #[test]
+  fn client_id_errors() {
+    assert_eq!(
+      "01".parse::<ClientId>(),
+      Err(Dhcpv4Error::TooShort {
+        expected: 2,
+        actual: 1
+      })
+    );
+    assert_eq!(
+      "ff:00:00:00".parse::<ClientId>(),
+      Err(Dhcpv4Error::TooShort {
+        expected: 5,
+        actual: 4
+      })
+    );
+    assert_eq!(
+      "ff:00:00:00:01:00".parse::<ClientId>(),
+      Err(Dhcpv4Error::Duid(DuidError::TooShort {
+        expected: 2,
+        actual: 1
+      }))
+    );
+    assert_eq!(
+      "01:0x".parse::<ClientId>(),
+      Err(Dhcpv4Error::InvalidText(4))
+    );
+    assert_eq!(ClientId::decode(&[0; 256]), Err(Dhcpv4Error::TooLong(256)));
+    assert_eq!(
+      ClientId::mac(MAC).encode(&mut [0; 6]),
+      Err(Dhcpv4Error::TooShort {
+        expected: 7,
+        actual: 6
+      })
+    );
+  }
+}
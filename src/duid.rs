@@ -0,0 +1,514 @@
+//! DHCP Unique Identifiers ([RFC 8415]), as used by DHCPv6 and by DHCPv4
+//! client identifiers ([RFC 4361]).
+//!
+//! DUID-LLT and DUID-LL embed a hardware type and a link-layer address,
+//! which may be a [`MacAddr`], an [`Eui64Addr`](crate::Eui64Addr) or an
+//! [`InfiniBandAddr`](crate::InfiniBandAddr); DUID-EN and DUID-UUID do not.
+//!
+//! [RFC 8415]: https://www.rfc-editor.org/rfc/rfc8415#section-11
+//! [RFC 4361]: https://www.rfc-editor.org/rfc/rfc4361
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hardware_address::{duid::Duid, MacAddr};
+//!
+//! let mac = MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]);
+//! let duid = Duid::llt(0x2b3c4d5e, mac);
+//! assert_eq!(duid.to_string(), "00:01:00:01:2b:3c:4d:5e:00:00:5e:00:53:01");
+//!
+//! let parsed: Duid = "00:03:00:01:00:00:5e:00:53:01".parse().unwrap();
+//! assert_eq!(parsed, Duid::ll(mac));
+//! assert_eq!(parsed.mac_addr(), Some(mac));
+//! ```
+
+use core::{fmt, str::FromStr};
+
+use crate::{
+  arp::HardwareType,
+  hex::{decode_hex, write_hex, HexError},
+  AnyHardwareAddr, MacAddr,
+};
+
+/// An error returned while decoding, encoding or parsing a DUID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+pub enum DuidError {
+  /// The input or buffer is too short.
+  #[error("buffer too short: expected at least {expected} bytes, but got {actual} bytes")]
+  TooShort {
+    /// The number of bytes needed.
+    expected: usize,
+    /// The actual number of bytes.
+    actual: usize,
+  },
+  /// The DUID is longer than 130 bytes.
+  #[error("DUID of {0} bytes is longer than 130 bytes")]
+  TooLong(usize),
+  /// The link-layer address of a DUID-LLT or DUID-LL is not of a supported
+  /// size, or a DUID-UUID is not 16 bytes.
+  #[error("invalid length of {len} bytes for DUID type {duid_type}")]
+  InvalidLength {
+    /// The DUID type.
+    duid_type: u16,
+    /// The length of the address or UUID.
+    len: usize,
+  },
+  /// The text is not colon-separated or bare hex octets.
+  #[error("invalid DUID text at offset {0}")]
+  InvalidText(usize),
+}
+
+/// The longest DUID, its type and 128 bytes.
+const MAX_LEN: usize = 130;
+
+/// The longest variable part of a DUID.
+const MAX_DATA_LEN: usize = MAX_LEN - 2;
+
+/// Variable-length DUID contents stored inline, at most 128 bytes.
+#[derive(Clone, Copy)]
+pub struct DuidData {
+  buf: [u8; MAX_DATA_LEN],
+  len: u8,
+}
+
+impl DuidData {
+  /// Copies `src`, returning `None` if it is longer than 128 bytes.
+  #[inline]
+  pub fn new(src: &[u8]) -> Option<Self> {
+    if src.len() > MAX_DATA_LEN {
+      return None;
+    }
+    let mut buf = [0; MAX_DATA_LEN];
+    buf[..src.len()].copy_from_slice(src);
+    Some(Self {
+      buf,
+      len: src.len() as u8,
+    })
+  }
+
+  /// Returns the bytes.
+  #[inline]
+  pub fn as_bytes(&self) -> &[u8] {
+    &self.buf[..self.len as usize]
+  }
+}
+
+impl fmt::Debug for DuidData {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_tuple("DuidData").field(&self.as_bytes()).finish()
+  }
+}
+
+impl PartialEq for DuidData {
+  #[inline]
+  fn eq(&self, other: &Self) -> bool {
+    self.as_bytes() == other.as_bytes()
+  }
+}
+
+impl Eq for DuidData {}
+
+impl core::hash::Hash for DuidData {
+  #[inline]
+  fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+    self.as_bytes().hash(state)
+  }
+}
+
+impl AsRef<[u8]> for DuidData {
+  #[inline]
+  fn as_ref(&self) -> &[u8] {
+    self.as_bytes()
+  }
+}
+
+/// A DHCP Unique Identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Duid {
+  /// DUID-LLT, type 1: a link-layer address plus the time it was
+  /// generated.
+  Llt {
+    /// The hardware type of the link.
+    hardware_type: HardwareType,
+    /// Seconds since midnight UTC, January 1, 2000, modulo 2^32.
+    time: u32,
+    /// The link-layer address.
+    addr: AnyHardwareAddr,
+  },
+  /// DUID-EN, type 2: assigned by the vendor with the given IANA
+  /// enterprise number.
+  En {
+    /// The IANA private enterprise number.
+    enterprise_number: u32,
+    /// The vendor-assigned identifier.
+    identifier: DuidData,
+  },
+  /// DUID-LL, type 3: a link-layer address.
+  Ll {
+    /// The hardware type of the link.
+    hardware_type: HardwareType,
+    /// The link-layer address.
+    addr: AnyHardwareAddr,
+  },
+  /// DUID-UUID, type 4: a UUID ([RFC 6355]).
+  ///
+  /// [RFC 6355]: https://www.rfc-editor.org/rfc/rfc6355
+  Uuid([u8; 16]),
+  /// A DUID of an unassigned type.
+  Other {
+    /// The DUID type.
+    duid_type: u16,
+    /// The contents after the type.
+    data: DuidData,
+  },
+}
+
+impl Duid {
+  /// Seconds from the Unix epoch to the DUID-LLT epoch, midnight UTC,
+  /// January 1, 2000.
+  pub const EPOCH: u64 = 946_684_800;
+
+  /// Creates a DUID-LL of `addr`, with the usual hardware type of such
+  /// addresses (see [`HardwareType::of`]).
+  #[inline]
+  pub fn ll(addr: impl Into<AnyHardwareAddr>) -> Self {
+    let addr = addr.into();
+    Self::Ll {
+      hardware_type: HardwareType::of(&addr),
+      addr,
+    }
+  }
+
+  /// Creates a DUID-LLT of `addr` generated at `time`, in seconds since
+  /// [`EPOCH`](Self::EPOCH), with the usual hardware type of such
+  /// addresses (see [`HardwareType::of`]).
+  #[inline]
+  pub fn llt(time: u32, addr: impl Into<AnyHardwareAddr>) -> Self {
+    let addr = addr.into();
+    Self::Llt {
+      hardware_type: HardwareType::of(&addr),
+      time,
+      addr,
+    }
+  }
+
+  /// Creates a DUID-LLT of `addr` generated now.
+  #[cfg(feature = "std")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+  pub fn llt_now(addr: impl Into<AnyHardwareAddr>) -> Self {
+    let unix = std::time::SystemTime::now()
+      .duration_since(std::time::UNIX_EPOCH)
+      .map_or(0, |d| d.as_secs());
+    Self::llt(Self::time_from_unix(unix), addr)
+  }
+
+  /// Converts seconds since the Unix epoch into a DUID-LLT time, modulo
+  /// 2^32.
+  #[inline]
+  pub const fn time_from_unix(secs: u64) -> u32 {
+    secs.wrapping_sub(Self::EPOCH) as u32
+  }
+
+  /// Returns the DUID type.
+  #[inline]
+  pub const fn duid_type(&self) -> u16 {
+    match self {
+      Self::Llt { .. } => 1,
+      Self::En { .. } => 2,
+      Self::Ll { .. } => 3,
+      Self::Uuid(_) => 4,
+      Self::Other { duid_type, .. } => *duid_type,
+    }
+  }
+
+  /// Returns the hardware type of a DUID-LLT or DUID-LL.
+  #[inline]
+  pub const fn hardware_type(&self) -> Option<HardwareType> {
+    match self {
+      Self::Llt { hardware_type, .. } | Self::Ll { hardware_type, .. } => Some(*hardware_type),
+      _ => None,
+    }
+  }
+
+  /// Returns the link-layer address of a DUID-LLT or DUID-LL.
+  #[inline]
+  pub const fn hardware_addr(&self) -> Option<AnyHardwareAddr> {
+    match self {
+      Self::Llt { addr, .. } | Self::Ll { addr, .. } => Some(*addr),
+      _ => None,
+    }
+  }
+
+  /// Returns the link-layer address of a DUID-LLT or DUID-LL if it is a
+  /// MAC address.
+  #[inline]
+  pub const fn mac_addr(&self) -> Option<MacAddr> {
+    match self.hardware_addr() {
+      Some(AnyHardwareAddr::Mac(addr)) => Some(addr),
+      _ => None,
+    }
+  }
+
+  /// Returns the length of the encoded DUID in bytes.
+  #[inline]
+  pub fn encoded_len(&self) -> usize {
+    2 + match self {
+      Self::Llt { addr, .. } => 6 + addr.size(),
+      Self::En { identifier, .. } => 4 + identifier.as_bytes().len(),
+      Self::Ll { addr, .. } => 2 + addr.size(),
+      Self::Uuid(_) => 16,
+      Self::Other { data, .. } => data.as_bytes().len(),
+    }
+  }
+
+  /// Decodes a DUID from its raw bytes, e.g. the contents of a DHCPv6
+  /// Client Identifier option.
+  pub fn decode(src: &[u8]) -> Result<Self, DuidError> {
+    let too_short = |expected| DuidError::TooShort {
+      expected,
+      actual: src.len(),
+    };
+    if src.len() < 2 {
+      return Err(too_short(2));
+    }
+    if src.len() > MAX_LEN {
+      return Err(DuidError::TooLong(src.len()));
+    }
+
+    let duid_type = u16::from_be_bytes([src[0], src[1]]);
+    let addr = |at: usize| {
+      AnyHardwareAddr::from_bytes(&src[at..]).ok_or(DuidError::InvalidLength {
+        duid_type,
+        len: src.len() - at,
+      })
+    };
+    let hardware_type = || HardwareType::from_u16(u16::from_be_bytes([src[2], src[3]]));
+    match duid_type {
+      1 => {
+        if src.len() < 8 {
+          return Err(too_short(8));
+        }
+        Ok(Self::Llt {
+          hardware_type: hardware_type(),
+          time: u32::from_be_bytes([src[4], src[5], src[6], src[7]]),
+          addr: addr(8)?,
+        })
+      }
+      2 => {
+        if src.len() < 6 {
+          return Err(too_short(6));
+        }
+        Ok(Self::En {
+          enterprise_number: u32::from_be_bytes([src[2], src[3], src[4], src[5]]),
+          identifier: DuidData::new(&src[6..]).unwrap(),
+        })
+      }
+      3 => {
+        if src.len() < 4 {
+          return Err(too_short(4));
+        }
+        Ok(Self::Ll {
+          hardware_type: hardware_type(),
+          addr: addr(4)?,
+        })
+      }
+      4 => match src[2..].try_into() {
+        Ok(uuid) => Ok(Self::Uuid(uuid)),
+        Err(_) => Err(DuidError::InvalidLength {
+          duid_type,
+          len: src.len() - 2,
+        }),
+      },
+      _ => Ok(Self::Other {
+        duid_type,
+        data: DuidData::new(&src[2..]).unwrap(),
+      }),
+    }
+  }
+
+  /// Encodes the DUID to the start of `buf`, returning its length.
+  pub fn encode(&self, buf: &mut [u8]) -> Result<usize, DuidError> {
+    let len = self.encoded_len();
+    if len > MAX_LEN {
+      return Err(DuidError::TooLong(len));
+    }
+    if buf.len() < len {
+      return Err(DuidError::TooShort {
+        expected: len,
+        actual: buf.len(),
+      });
+    }
+
+    buf[..2].copy_from_slice(&self.duid_type().to_be_bytes());
+    let rest = &mut buf[2..len];
+    match self {
+      Self::Llt {
+        hardware_type,
+        time,
+        addr,
+      } => {
+        rest[..2].copy_from_slice(&hardware_type.to_u16().to_be_bytes());
+        rest[2..6].copy_from_slice(&time.to_be_bytes());
+        rest[6..].copy_from_slice(addr.as_bytes());
+      }
+      Self::En {
+        enterprise_number,
+        identifier,
+      } => {
+        rest[..4].copy_from_slice(&enterprise_number.to_be_bytes());
+        rest[4..].copy_from_slice(identifier.as_bytes());
+      }
+      Self::Ll {
+        hardware_type,
+        addr,
+      } => {
+        rest[..2].copy_from_slice(&hardware_type.to_u16().to_be_bytes());
+        rest[2..].copy_from_slice(addr.as_bytes());
+      }
+      Self::Uuid(uuid) => rest.copy_from_slice(uuid),
+      Self::Other { data, .. } => rest.copy_from_slice(data.as_bytes()),
+    }
+    Ok(len)
+  }
+}
+
+/// Parses colon-separated (`00:03:00:01:00:00:5e:00:53:01`) or bare
+/// (`00030001...`) hex octets.
+impl FromStr for Duid {
+  type Err = DuidError;
+
+  fn from_str(src: &str) -> Result<Self, Self::Err> {
+    let mut buf = [0u8; MAX_LEN];
+    match decode_hex(src, &mut buf) {
+      Ok(len) => Self::decode(&buf[..len]),
+      Err(HexError::InvalidText(at)) => Err(DuidError::InvalidText(at)),
+      Err(HexError::TooLong(len)) => Err(DuidError::TooLong(len)),
+    }
+  }
+}
+
+/// Formats the DUID as colon-separated lowercase hex octets.
+impl fmt::Display for Duid {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut buf = [0u8; MAX_LEN];
+    match self.encode(&mut buf) {
+      Ok(len) => write_hex(&buf[..len], f),
+      Err(_) => Err(fmt::Error),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{Eui64Addr, InfiniBandAddr};
+
+  use std::string::ToString;
+
+  const MAC: MacAddr = MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]);
+
+  fn round_trip(duid: Duid) {
+    let mut buf = [0u8; MAX_LEN];
+    let len = duid.encode(&mut buf).unwrap();
+    assert_eq!(len, duid.encoded_len());
+    assert_eq!(Duid::decode(&buf[..len]), Ok(duid));
+    assert_eq!(duid.to_string().parse(), Ok(duid));
+  }
+
+  #[test]
+  fn link_layer() {
+    let duid = Duid::llt(0x2b3c4d5e, MAC);
+    assert_eq!(duid.duid_type(), 1);
+    assert_eq!(duid.hardware_type(), Some(HardwareType::Ethernet));
+    assert_eq!(duid.mac_addr(), Some(MAC));
+    round_trip(duid);
+
+    let ib = InfiniBandAddr::from_raw([0x11; 20]);
+    let duid = Duid::ll(ib);
+    assert_eq!(duid.hardware_type(), Some(HardwareType::InfiniBand));
+    assert_eq!(duid.hardware_addr(), Some(ib.into()));
+    assert_eq!(duid.mac_addr(), None);
+    assert_eq!(duid.encoded_len(), 24);
+    round_trip(duid);
+
+    let eui = Eui64Addr::from_raw([0x02, 0x00, 0x5e, 0x10, 0x00, 0x00, 0x00, 0x01]);
+    let duid: Duid = "00:01:00:1b:00:00:00:00:02:00:5e:10:00:00:00:01"
+      .parse()
+      .unwrap();
+    assert_eq!(duid, Duid::llt(0, eui));
+    assert_eq!(duid.hardware_type(), Some(HardwareType::Eui64));
+  }
+
+  #[test]
+  fn en_uuid_and_other() {
+    let duid: Duid = "0002000000090c4f5f6e6f6465".parse().unwrap();
+    assert_eq!(
+      duid,
+      Duid::En {
+        enterprise_number: 9,
+        identifier: DuidData::new(b"\x0cO_node").unwrap(),
+      }
+    );
+    assert_eq!(duid.hardware_addr(), None);
+    round_trip(duid);
+
+    let uuid = Duid::Uuid([0xab; 16]);
+    assert_eq!(uuid.encoded_len(), 18);
+    round_trip(uuid);
+
+    let other = Duid::decode(&[0x00, 0x07, 0x01]).unwrap();
+    assert_eq!(other.duid_type(), 7);
+    round_trip(other);
+  }
+
+  #[test]
+  fn time() {
+    assert_eq!(Duid::time_from_unix(Duid::EPOCH + 42), 42);
+    // 2024-01-01T00:00:00Z
+    assert_eq!(Duid::time_from_unix(1_704_067_200), 757_382_400);
+  }
+
+  #[test]
+  fn errors() {
+    assert_eq!(
+      Duid::decode(&[0]),
+      Err(DuidError::TooShort {
+        expected: 2,
+        actual: 1
+      })
+    );
+    assert_eq!(
+      Duid::decode(&[0, 1, 0, 1, 0, 0]),
+      Err(DuidError::TooShort {
+        expected: 8,
+        actual: 6
+      })
+    );
+    assert_eq!(
+      Duid::decode(&[0, 3, 0, 1, 0, 0, 0, 0, 0]),
+      Err(DuidError::InvalidLength {
+        duid_type: 3,
+        len: 5
+      })
+    );
+    assert_eq!(
+      Duid::decode(&[0, 4, 0]),
+      Err(DuidError::InvalidLength {
+        duid_type: 4,
+        len: 1
+      })
+    );
+    assert_eq!(Duid::decode(&[0; 131]), Err(DuidError::TooLong(131)));
+
+    assert_eq!("00:0".parse::<Duid>(), Err(DuidError::InvalidText(4)));
+    assert_eq!("00:03-00".parse::<Duid>(), Err(DuidError::InvalidText(5)));
+    assert_eq!("000g".parse::<Duid>(), Err(DuidError::InvalidText(3)));
+    assert_eq!(
+      "".parse::<Duid>(),
+      Err(DuidError::TooShort {
+        expected: 2,
+        actual: 0
+      })
+    );
+  }
+}
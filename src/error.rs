@@ -0,0 +1,448 @@
+use core::fmt;
+
+use crate::{Case, Notation};
+
+/// The kind of a [`ParseError`] or [`AddrParseError`], independent of the
+/// address size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseErrorKind {
+  /// The input has a length no notation of the address can have. Holds the
+  /// length of the input.
+  InvalidLength(usize),
+  /// A separator differs from the first separator of the input.
+  UnexpectedSeparator {
+    /// The expected separator.
+    expected: u8,
+    /// The actual separator.
+    actual: u8,
+  },
+  /// A byte where a separator is expected is not a valid separator.
+  InvalidSeparator(u8),
+  /// A byte where a hex digit is expected is not a hex digit.
+  InvalidHexDigit(u8),
+  /// The input is in a notation the [`ParseOptions`](crate::ParseOptions) do
+  /// not allow.
+  DisallowedNotation(Notation),
+  /// A hex digit does not have the case required by the
+  /// [`ParseOptions`](crate::ParseOptions).
+  UnexpectedCase {
+    /// The required case.
+    expected: Case,
+    /// The offending hex digit.
+    actual: u8,
+  },
+}
+
+impl fmt::Display for ParseErrorKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match *self {
+      Self::InvalidLength(len) => write!(f, "invalid length of {len} bytes"),
+      Self::UnexpectedSeparator { expected, actual } => write!(
+        f,
+        "unexpected separator: expected {}, but got {}",
+        Byte(expected),
+        Byte(actual)
+      ),
+      Self::InvalidSeparator(sep) => write!(f, "invalid separator {}", Byte(sep)),
+      Self::InvalidHexDigit(digit) => write!(f, "invalid hex digit {}", Byte(digit)),
+      Self::DisallowedNotation(notation) => write!(f, "{notation} notation is not allowed"),
+      Self::UnexpectedCase { expected, actual } => write!(
+        f,
+        "unexpected case: expected {} hex digit, but got {}",
+        match expected {
+          Case::Lower => "a lowercase",
+          Case::Upper => "an uppercase",
+        },
+        Byte(actual)
+      ),
+    }
+  }
+}
+
+/// An error that occurred while parsing an address of `N` octets.
+///
+/// Besides its [`kind`](Self::kind), it records the byte
+/// [`offset`](Self::offset) of the offending input and the
+/// [`notation`](Self::notation) that was being parsed, if it was known at that
+/// point. Convert it into an [`AddrParseError`] to handle errors of several
+/// address sizes with one type.
+///
+/// ## Example
+///
+/// ```rust
+/// use hardware_address::{MacAddr, Notation, ParseErrorKind};
+///
+/// let err = "00:00:5e:00:5g:01".parse::<MacAddr>().unwrap_err();
+/// assert_eq!(err.kind(), ParseErrorKind::InvalidHexDigit(b'g'));
+/// assert_eq!(err.offset(), 13);
+/// assert_eq!(err.notation(), Some(Notation::Colon));
+/// assert_eq!(
+///   err.to_string(),
+///   "invalid hex digit 'g' at offset 13 in colon-separated address",
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+#[error("{}", ErrorDisplay::new(N, .kind, .offset, .notation))]
+pub struct ParseError<const N: usize> {
+  kind: ParseErrorKind,
+  offset: usize,
+  notation: Option<Notation>,
+}
+
+impl<const N: usize> ParseError<N> {
+  /// Creates an error of `kind` at byte `offset`, with no notation.
+  #[inline]
+  pub const fn new(kind: ParseErrorKind, offset: usize) -> Self {
+    Self {
+      kind,
+      offset,
+      notation: None,
+    }
+  }
+
+  /// Sets the notation that was being parsed.
+  #[inline]
+  pub const fn with_notation(mut self, notation: Option<Notation>) -> Self {
+    self.notation = notation;
+    self
+  }
+
+  /// Returns an error for an input of invalid length `len`.
+  #[inline]
+  pub const fn invalid_length(len: usize) -> Self {
+    Self::new(ParseErrorKind::InvalidLength(len), 0)
+  }
+
+  /// Returns an error for an unexpected separator at `offset`.
+  #[inline]
+  pub const fn unexpected_separator(expected: u8, actual: u8, offset: usize) -> Self {
+    Self::new(
+      ParseErrorKind::UnexpectedSeparator { expected, actual },
+      offset,
+    )
+  }
+
+  /// Returns an error for an invalid separator at `offset`.
+  #[inline]
+  pub const fn invalid_separator(sep: u8, offset: usize) -> Self {
+    Self::new(ParseErrorKind::InvalidSeparator(sep), offset)
+  }
+
+  /// Returns an error for an invalid hex digit at `offset`.
+  #[inline]
+  pub const fn invalid_hex_digit(digit: u8, offset: usize) -> Self {
+    Self::new(ParseErrorKind::InvalidHexDigit(digit), offset)
+  }
+
+  /// Returns an error for a notation that is not allowed.
+  #[inline]
+  pub const fn disallowed_notation(notation: Notation) -> Self {
+    Self::new(ParseErrorKind::DisallowedNotation(notation), 0).with_notation(Some(notation))
+  }
+
+  /// Returns an error for a hex digit of the wrong case at `offset`.
+  #[inline]
+  pub const fn unexpected_case(expected: Case, actual: u8, offset: usize) -> Self {
+    Self::new(ParseErrorKind::UnexpectedCase { expected, actual }, offset)
+  }
+
+  /// Returns the kind of the error.
+  #[inline]
+  pub const fn kind(&self) -> ParseErrorKind {
+    self.kind
+  }
+
+  /// Returns the byte offset of the offending input.
+  ///
+  /// It is `0` for [`ParseErrorKind::InvalidLength`] and
+  /// [`ParseErrorKind::DisallowedNotation`], which concern the whole input.
+  #[inline]
+  pub const fn offset(&self) -> usize {
+    self.offset
+  }
+
+  /// Returns the notation that was being parsed, if it was known.
+  #[inline]
+  pub const fn notation(&self) -> Option<Notation> {
+    self.notation
+  }
+
+  /// Returns the number of octets of the address that was being parsed.
+  #[inline]
+  pub const fn size(&self) -> usize {
+    N
+  }
+}
+
+/// A [`ParseError`] of any address size.
+///
+/// Every `ParseError<N>` converts into it, so a function parsing several
+/// kinds of addresses can return a single error type.
+///
+/// ## Example
+///
+/// ```rust
+/// use hardware_address::{AddrParseError, Eui64Addr, MacAddr};
+///
+/// fn parse_both(mac: &str, eui: &str) -> Result<(MacAddr, Eui64Addr), AddrParseError> {
+///   Ok((mac.parse()?, eui.parse()?))
+/// }
+///
+/// let err = parse_both("00:00:5e:00:53:01", "02:00:5e:10:00:00:00").unwrap_err();
+/// assert_eq!(err.size(), 8);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+#[error("{}", ErrorDisplay::new(*.size, .kind, .offset, .notation))]
+pub struct AddrParseError {
+  size: usize,
+  kind: ParseErrorKind,
+  offset: usize,
+  notation: Option<Notation>,
+}
+
+impl AddrParseError {
+  /// Returns the kind of the error.
+  #[inline]
+  pub const fn kind(&self) -> ParseErrorKind {
+    self.kind
+  }
+
+  /// Returns the byte offset of the offending input.
+  #[inline]
+  pub const fn offset(&self) -> usize {
+    self.offset
+  }
+
+  /// Returns the notation that was being parsed, if it was known.
+  #[inline]
+  pub const fn notation(&self) -> Option<Notation> {
+    self.notation
+  }
+
+  /// Returns the number of octets of the address that was being parsed.
+  #[inline]
+  pub const fn size(&self) -> usize {
+    self.size
+  }
+}
+
+impl<const N: usize> From<ParseError<N>> for AddrParseError {
+  #[inline]
+  fn from(err: ParseError<N>) -> Self {
+    Self {
+      size: N,
+      kind: err.kind,
+      offset: err.offset,
+      notation: err.notation,
+    }
+  }
+}
+
+/// An error returned when a byte slice cannot be viewed as, or converted
+/// into, addresses because of its length.
+///
+/// Returned by the `TryFrom<&[u8]>` implementations and the
+/// `ref_from_bytes`/`slice_from_bytes` methods of the address types.
+///
+/// ## Example
+///
+/// ```rust
+/// use hardware_address::MacAddr;
+///
+/// let err = MacAddr::slice_from_bytes(&[0; 13]).unwrap_err();
+/// assert_eq!((err.size(), err.actual()), (6, 13));
+/// assert_eq!(
+///   err.to_string(),
+///   "invalid length: expected a multiple of 6 bytes, but got 13 bytes",
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+#[error(
+  "invalid length: expected {}{} bytes, but got {} bytes",
+  if *.multiple { "a multiple of " } else { "" },
+  .size,
+  .actual
+)]
+pub struct SliceLengthError {
+  size: usize,
+  actual: usize,
+  multiple: bool,
+}
+
+impl SliceLengthError {
+  /// Returns an error for a slice of `actual` bytes where exactly `size`
+  /// bytes are expected.
+  #[inline]
+  pub const fn exact(size: usize, actual: usize) -> Self {
+    Self {
+      size,
+      actual,
+      multiple: false,
+    }
+  }
+
+  /// Returns an error for a slice of `actual` bytes where a multiple of
+  /// `size` bytes is expected.
+  #[inline]
+  pub const fn multiple_of(size: usize, actual: usize) -> Self {
+    Self {
+      size,
+      actual,
+      multiple: true,
+    }
+  }
+
+  /// Returns the size of the address in bytes.
+  #[inline]
+  pub const fn size(&self) -> usize {
+    self.size
+  }
+
+  /// Returns the length of the offending slice.
+  #[inline]
+  pub const fn actual(&self) -> usize {
+    self.actual
+  }
+
+  /// Returns `true` if any multiple of [`size`](Self::size) bytes would
+  /// have been accepted.
+  #[inline]
+  pub const fn is_multiple(&self) -> bool {
+    self.multiple
+  }
+}
+
+struct ErrorDisplay {
+  size: usize,
+  kind: ParseErrorKind,
+  offset: usize,
+  notation: Option<Notation>,
+}
+
+impl ErrorDisplay {
+  #[inline]
+  fn new(size: usize, kind: &ParseErrorKind, offset: &usize, notation: &Option<Notation>) -> Self {
+    Self {
+      size,
+      kind: *kind,
+      offset: *offset,
+      notation: *notation,
+    }
+  }
+}
+
+impl fmt::Display for ErrorDisplay {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.kind {
+      ParseErrorKind::InvalidLength(len) => {
+        let n = self.size;
+        write!(
+          f,
+          "invalid length: a {n}-octet address is {} bytes colon or hyphen separated, {} bytes dot separated or {} bytes bare hex ({} with a `0x` prefix), but got {len} bytes",
+          (n * 3).saturating_sub(1),
+          (n * 2 + n / 2).saturating_sub(1),
+          n * 2,
+          n * 2 + 2,
+        )
+      }
+      ParseErrorKind::DisallowedNotation(_) => self.kind.fmt(f),
+      _ => {
+        write!(f, "{} at offset {}", self.kind, self.offset)?;
+        match self.notation {
+          Some(notation) => write!(f, " in {notation} address"),
+          None => Ok(()),
+        }
+      }
+    }
+  }
+}
+
+/// Prints a byte of the input as a quoted character, escaping it if it is
+/// not printable ASCII, e.g. `'g'` or `'\x80'`.
+struct Byte(u8);
+
+impl fmt::Display for Byte {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use fmt::Write;
+
+    f.write_char('\'')?;
+    for c in core::ascii::escape_default(self.0) {
+      f.write_char(c as char)?;
+    }
+    f.write_char('\'')
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use std::string::ToString;
+
+  #[test]
+  fn display() {
+    let cases: [(ParseError<6>, &str); 7] = [
+      (
+        ParseError::invalid_length(18),
+        "invalid length: a 6-octet address is 17 bytes colon or hyphen separated, 14 bytes dot separated or 12 bytes bare hex (14 with a `0x` prefix), but got 18 bytes",
+      ),
+      (
+        ParseError::unexpected_separator(b'-', b':', 5).with_notation(Some(Notation::Hyphen)),
+        "unexpected separator: expected '-', but got ':' at offset 5 in hyphen-separated address",
+      ),
+      (
+        ParseError::invalid_separator(b'.', 2),
+        "invalid separator '.' at offset 2",
+      ),
+      (
+        ParseError::invalid_hex_digit(0x80, 3).with_notation(Some(Notation::BareHex)),
+        "invalid hex digit '\\x80' at offset 3 in bare hex address",
+      ),
+      (
+        ParseError::invalid_hex_digit(b'\n', 0),
+        "invalid hex digit '\\n' at offset 0",
+      ),
+      (
+        ParseError::disallowed_notation(Notation::Dot),
+        "dot-separated notation is not allowed",
+      ),
+      (
+        ParseError::unexpected_case(Case::Upper, b'e', 7).with_notation(Some(Notation::Dot)),
+        "unexpected case: expected an uppercase hex digit, but got 'e' at offset 7 in dot-separated address",
+      ),
+    ];
+    for (err, expected) in cases {
+      assert_eq!(err.to_string(), expected);
+      assert_eq!(AddrParseError::from(err).to_string(), expected);
+    }
+  }
+
+  #[test]
+  fn into_addr_parse_error() {
+    let err = ParseError::<8>::invalid_hex_digit(b'g', 4).with_notation(Some(Notation::Colon));
+    assert_eq!(err.size(), 8);
+    let any = AddrParseError::from(err);
+    assert_eq!(any.size(), 8);
+    assert_eq!(any.kind(), ParseErrorKind::InvalidHexDigit(b'g'));
+    assert_eq!(any.offset(), 4);
+    assert_eq!(any.notation(), Some(Notation::Colon));
+
+    // Same error for a different address size.
+    let other = ParseError::<6>::invalid_hex_digit(b'g', 4).with_notation(Some(Notation::Colon));
+    assert_ne!(any, AddrParseError::from(other));
+  }
+
+  #[test]
+  fn slice_length_display() {
+    assert_eq!(
+      SliceLengthError::exact(8, 6).to_string(),
+      "invalid length: expected 8 bytes, but got 6 bytes"
+    );
+    let err = SliceLengthError::multiple_of(6, 7);
+    assert!(err.is_multiple());
+    assert_eq!(
+      err.to_string(),
+      "invalid length: expected a multiple of 6 bytes, but got 7 bytes"
+    );
+  }
+}
@@ -0,0 +1,806 @@
+//! Ethernet II and IEEE 802.3 frame headers, with 802.1Q and 802.1ad VLAN
+//! tags.
+//!
+//! [`EthernetHeader`] is a zero-copy view of the header at the start of a
+//! frame, [`EthernetHeaderBuilder`] writes one. Neither allocates, so both
+//! work in `no_std`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hardware_address::{
+//!   ethernet::{EtherType, EthernetHeader, EthernetHeaderBuilder, TypeOrLength, VlanTag},
+//!   MacAddr,
+//! };
+//!
+//! let header = EthernetHeaderBuilder::new(
+//!   MacAddr::from_raw([0xff; 6]),
+//!   MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]),
+//!   TypeOrLength::EtherType(EtherType::Arp),
+//! )
+//! .with_vlan_tag(VlanTag::new(100).with_pcp(5));
+//!
+//! let mut frame = [0u8; 64];
+//! let len = header.write(&mut frame).unwrap();
+//! assert_eq!(len, 18);
+//!
+//! let parsed = EthernetHeader::parse(&frame).unwrap();
+//! assert!(parsed.destination().is_multicast());
+//! assert_eq!(parsed.vlan_tags().next().unwrap().vid(), 100);
+//! assert_eq!(parsed.ether_type(), Some(EtherType::Arp));
+//! assert_eq!(parsed.payload().len(), 64 - 18);
+//! ```
+
+use core::iter::FusedIterator;
+
+use crate::MacAddr;
+
+/// An error returned while reading or writing an Ethernet header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+pub enum EthernetError {
+  /// The buffer is too short for the header.
+  #[error("buffer too short: expected at least {expected} bytes, but got {actual} bytes")]
+  TooShort {
+    /// The number of bytes the header needs.
+    expected: usize,
+    /// The length of the buffer.
+    actual: usize,
+  },
+  /// The EtherType/length field is neither a valid 802.3 length (at most
+  /// 1500) nor an EtherType (at least 1536).
+  #[error("invalid EtherType or length {0:#06x}")]
+  InvalidTypeOrLength(u16),
+}
+
+macro_rules! ether_types {
+  ($($(#[$attr:meta])* $variant:ident = $value:literal),+ $(,)?) => {
+    /// An EtherType, identifying the protocol of the payload of a frame.
+    ///
+    /// Values without a variant of their own are kept in
+    /// [`Other`](Self::Other). Comparison, ordering and hashing go by the
+    /// numeric value, so `Other(0x0800)` equals [`Ipv4`](Self::Ipv4), but a
+    /// `match` only sees the canonical variant, which
+    /// [`from_u16`](Self::from_u16) returns.
+    #[derive(Debug, Clone, Copy)]
+    #[non_exhaustive]
+    pub enum EtherType {
+      $(
+        $(#[$attr])*
+        $variant,
+      )+
+      /// Any other EtherType.
+      Other(u16),
+    }
+
+    impl EtherType {
+      /// Returns the EtherType of `value`.
+      #[inline]
+      pub const fn from_u16(value: u16) -> Self {
+        match value {
+          $($value => Self::$variant,)+
+          other => Self::Other(other),
+        }
+      }
+
+      /// Returns the numeric value.
+      #[inline]
+      pub const fn to_u16(self) -> u16 {
+        match self {
+          $(Self::$variant => $value,)+
+          Self::Other(other) => other,
+        }
+      }
+    }
+  };
+}
+
+ether_types! {
+  /// Internet Protocol version 4.
+  Ipv4 = 0x0800,
+  /// Address Resolution Protocol.
+  Arp = 0x0806,
+  /// Wake-on-LAN magic packet.
+  WakeOnLan = 0x0842,
+  /// Reverse Address Resolution Protocol.
+  Rarp = 0x8035,
+  /// IEEE 802.1Q customer VLAN tag (C-tag).
+  Vlan = 0x8100,
+  /// Internet Protocol version 6.
+  Ipv6 = 0x86DD,
+  /// IEEE 802.3x flow control.
+  FlowControl = 0x8808,
+  /// MPLS unicast.
+  Mpls = 0x8847,
+  /// MPLS multicast.
+  MplsMulticast = 0x8848,
+  /// PPPoE discovery stage.
+  PppoeDiscovery = 0x8863,
+  /// PPPoE session stage.
+  PppoeSession = 0x8864,
+  /// IEEE 802.1X EAP over LAN.
+  Eapol = 0x888E,
+  /// IEEE 802.1ad service VLAN tag (S-tag).
+  ServiceVlan = 0x88A8,
+  /// Link Layer Discovery Protocol.
+  Lldp = 0x88CC,
+  /// IEEE 802.1AE MAC security.
+  MacSec = 0x88E5,
+  /// Precision Time Protocol.
+  Ptp = 0x88F7,
+}
+
+impl EtherType {
+  /// Returns `true` for the tag protocol identifiers that start a VLAN tag,
+  /// [`Vlan`](Self::Vlan) and [`ServiceVlan`](Self::ServiceVlan).
+  #[inline]
+  pub const fn is_vlan_tpid(self) -> bool {
+    matches!(
+      Self::from_u16(self.to_u16()),
+      Self::Vlan | Self::ServiceVlan
+    )
+  }
+}
+
+impl PartialEq for EtherType {
+  #[inline]
+  fn eq(&self, other: &Self) -> bool {
+    self.to_u16() == other.to_u16()
+  }
+}
+
+impl Eq for EtherType {}
+
+impl core::hash::Hash for EtherType {
+  #[inline]
+  fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+    self.to_u16().hash(state)
+  }
+}
+
+impl PartialOrd for EtherType {
+  #[inline]
+  fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for EtherType {
+  #[inline]
+  fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+    self.to_u16().cmp(&other.to_u16())
+  }
+}
+
+impl From<u16> for EtherType {
+  #[inline]
+  fn from(value: u16) -> Self {
+    Self::from_u16(value)
+  }
+}
+
+impl From<EtherType> for u16 {
+  #[inline]
+  fn from(value: EtherType) -> Self {
+    value.to_u16()
+  }
+}
+
+/// The field after the addresses and VLAN tags of a frame: an EtherType in
+/// Ethernet II frames, or the payload length in IEEE 802.3 frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypeOrLength {
+  /// An Ethernet II frame with the given EtherType.
+  EtherType(EtherType),
+  /// An IEEE 802.3 frame with a payload of the given length, usually
+  /// followed by an LLC header.
+  Length(u16),
+}
+
+impl TypeOrLength {
+  /// The largest value that is a length.
+  pub const MAX_LENGTH: u16 = 1500;
+
+  /// The smallest value that is an EtherType.
+  pub const MIN_ETHER_TYPE: u16 = 0x0600;
+
+  /// Interprets the field value, returning `None` if it is neither a
+  /// length nor an EtherType.
+  #[inline]
+  pub const fn from_u16(value: u16) -> Option<Self> {
+    if value <= Self::MAX_LENGTH {
+      Some(Self::Length(value))
+    } else if value >= Self::MIN_ETHER_TYPE {
+      Some(Self::EtherType(EtherType::from_u16(value)))
+    } else {
+      None
+    }
+  }
+
+  /// Returns the field value.
+  #[inline]
+  pub const fn to_u16(self) -> u16 {
+    match self {
+      Self::EtherType(ty) => ty.to_u16(),
+      Self::Length(len) => len,
+    }
+  }
+}
+
+/// An IEEE 802.1Q or 802.1ad VLAN tag: the tag protocol identifier (TPID)
+/// and the priority code point (PCP), drop eligible indicator (DEI) and
+/// VLAN identifier (VID) of the tag control information (TCI).
+///
+/// ## Example
+///
+/// ```rust
+/// use hardware_address::ethernet::{EtherType, VlanTag};
+///
+/// let tag = VlanTag::service(10).with_pcp(3).with_dei(true);
+/// assert_eq!(tag.tpid(), EtherType::ServiceVlan);
+/// assert_eq!(tag.tci(), 0x7 << 12 | 10);
+/// assert_eq!(VlanTag::from_tci(EtherType::ServiceVlan, tag.tci()), tag);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VlanTag {
+  tpid: EtherType,
+  tci: u16,
+}
+
+impl VlanTag {
+  /// The length of a tag on the wire in bytes.
+  pub const LEN: usize = 4;
+
+  /// The largest VLAN identifier. `0` means the frame only carries a
+  /// priority and `4095` is reserved.
+  pub const MAX_VID: u16 = 0x0FFF;
+
+  /// Creates an 802.1Q customer tag (C-tag) for VLAN `vid`, with priority
+  /// `0` and the DEI cleared.
+  ///
+  /// ## Panics
+  ///
+  /// Panics if `vid` is greater than [`MAX_VID`](Self::MAX_VID).
+  #[inline]
+  pub const fn new(vid: u16) -> Self {
+    Self {
+      tpid: EtherType::Vlan,
+      tci: 0,
+    }
+    .with_vid(vid)
+  }
+
+  /// Creates an 802.1ad service tag (S-tag) for VLAN `vid`, the outer tag
+  /// of a QinQ frame.
+  ///
+  /// ## Panics
+  ///
+  /// Panics if `vid` is greater than [`MAX_VID`](Self::MAX_VID).
+  #[inline]
+  pub const fn service(vid: u16) -> Self {
+    Self::new(vid).with_tpid(EtherType::ServiceVlan)
+  }
+
+  /// Creates a tag from its TPID and raw TCI.
+  #[inline]
+  pub const fn from_tci(tpid: EtherType, tci: u16) -> Self {
+    Self { tpid, tci }
+  }
+
+  /// Sets the tag protocol identifier.
+  ///
+  /// Default is [`EtherType::Vlan`].
+  #[inline]
+  pub const fn with_tpid(mut self, tpid: EtherType) -> Self {
+    self.tpid = tpid;
+    self
+  }
+
+  /// Sets the priority code point.
+  ///
+  /// ## Panics
+  ///
+  /// Panics if `pcp` is greater than `7`.
+  #[inline]
+  pub const fn with_pcp(mut self, pcp: u8) -> Self {
+    assert!(pcp < 8, "`pcp` must be less than 8");
+    self.tci = (self.tci & 0x1FFF) | (pcp as u16) << 13;
+    self
+  }
+
+  /// Sets the drop eligible indicator.
+  #[inline]
+  pub const fn with_dei(mut self, dei: bool) -> Self {
+    self.tci = (self.tci & !0x1000) | (dei as u16) << 12;
+    self
+  }
+
+  /// Sets the VLAN identifier.
+  ///
+  /// ## Panics
+  ///
+  /// Panics if `vid` is greater than [`MAX_VID`](Self::MAX_VID).
+  #[inline]
+  pub const fn with_vid(mut self, vid: u16) -> Self {
+    assert!(vid <= Self::MAX_VID, "`vid` must be at most 4095");
+    self.tci = (self.tci & !Self::MAX_VID) | vid;
+    self
+  }
+
+  /// Returns the tag protocol identifier.
+  #[inline]
+  pub const fn tpid(&self) -> EtherType {
+    self.tpid
+  }
+
+  /// Returns the raw tag control information.
+  #[inline]
+  pub const fn tci(&self) -> u16 {
+    self.tci
+  }
+
+  /// Returns the priority code point.
+  #[inline]
+  pub const fn pcp(&self) -> u8 {
+    (self.tci >> 13) as u8
+  }
+
+  /// Returns the drop eligible indicator.
+  #[inline]
+  pub const fn dei(&self) -> bool {
+    self.tci & 0x1000 != 0
+  }
+
+  /// Returns the VLAN identifier.
+  #[inline]
+  pub const fn vid(&self) -> u16 {
+    self.tci & Self::MAX_VID
+  }
+
+  #[inline]
+  fn write(&self, buf: &mut [u8]) {
+    buf[..2].copy_from_slice(&self.tpid.to_u16().to_be_bytes());
+    buf[2..4].copy_from_slice(&self.tci.to_be_bytes());
+  }
+}
+
+/// A zero-copy view of the Ethernet II or IEEE 802.3 header at the start of
+/// a frame.
+///
+/// VLAN tags are recognized by their TPID, [`EtherType::Vlan`] or
+/// [`EtherType::ServiceVlan`], and may be stacked to any depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EthernetHeader<'a> {
+  frame: &'a [u8],
+  tags: usize,
+}
+
+impl<'a> EthernetHeader<'a> {
+  /// The length of an untagged header in bytes.
+  pub const MIN_LEN: usize = 14;
+
+  /// Parses the header at the start of `frame`.
+  pub fn parse(frame: &'a [u8]) -> Result<Self, EthernetError> {
+    let mut tags = 0;
+    loop {
+      let at = 12 + tags * VlanTag::LEN;
+      let expected = at + 2;
+      if frame.len() < expected {
+        return Err(EthernetError::TooShort {
+          expected,
+          actual: frame.len(),
+        });
+      }
+      let value = u16::from_be_bytes([frame[at], frame[at + 1]]);
+      if !EtherType::from_u16(value).is_vlan_tpid() {
+        if TypeOrLength::from_u16(value).is_none() {
+          return Err(EthernetError::InvalidTypeOrLength(value));
+        }
+        return Ok(Self { frame, tags });
+      }
+      tags += 1;
+    }
+  }
+
+  /// Returns the destination address.
+  #[inline]
+  pub fn destination(&self) -> &'a MacAddr {
+    MacAddr::ref_from_bytes(&self.frame[..6]).unwrap()
+  }
+
+  /// Returns the source address.
+  #[inline]
+  pub fn source(&self) -> &'a MacAddr {
+    MacAddr::ref_from_bytes(&self.frame[6..12]).unwrap()
+  }
+
+  /// Returns the VLAN tags, outermost first.
+  #[inline]
+  pub fn vlan_tags(&self) -> VlanTags<'a> {
+    VlanTags {
+      src: &self.frame[12..12 + self.tags * VlanTag::LEN],
+    }
+  }
+
+  /// Returns the EtherType or 802.3 length field.
+  #[inline]
+  pub fn type_or_length(&self) -> TypeOrLength {
+    let at = self.header_len() - 2;
+    let value = u16::from_be_bytes([self.frame[at], self.frame[at + 1]]);
+    match TypeOrLength::from_u16(value) {
+      Some(field) => field,
+      // Checked by `parse`.
+      None => unreachable!(),
+    }
+  }
+
+  /// Returns the EtherType of an Ethernet II frame, or `None` for an 802.3
+  /// frame.
+  #[inline]
+  pub fn ether_type(&self) -> Option<EtherType> {
+    match self.type_or_length() {
+      TypeOrLength::EtherType(ty) => Some(ty),
+      TypeOrLength::Length(_) => None,
+    }
+  }
+
+  /// Returns the length of the header including its VLAN tags.
+  #[inline]
+  pub const fn header_len(&self) -> usize {
+    Self::MIN_LEN + self.tags * VlanTag::LEN
+  }
+
+  /// Returns the bytes of the header.
+  #[inline]
+  pub fn as_bytes(&self) -> &'a [u8] {
+    &self.frame[..self.header_len()]
+  }
+
+  /// Returns a builder for the same header, or `None` if it has more than
+  /// two VLAN tags.
+  pub fn to_builder(&self) -> Option<EthernetHeaderBuilder> {
+    let mut builder =
+      EthernetHeaderBuilder::new(*self.destination(), *self.source(), self.type_or_length());
+    for tag in self.vlan_tags() {
+      if builder.tag_count == 2 {
+        return None;
+      }
+      builder = builder.with_vlan_tag(tag);
+    }
+    Some(builder)
+  }
+
+  /// Returns the bytes after the header.
+  ///
+  /// For an 802.3 frame this may be longer than the
+  /// [`Length`](TypeOrLength::Length), e.g. because of padding.
+  #[inline]
+  pub fn payload(&self) -> &'a [u8] {
+    &self.frame[self.header_len()..]
+  }
+}
+
+/// An iterator over the VLAN tags of an [`EthernetHeader`], outermost first.
+#[derive(Debug, Clone)]
+pub struct VlanTags<'a> {
+  src: &'a [u8],
+}
+
+impl Iterator for VlanTags<'_> {
+  type Item = VlanTag;
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.src.len() < VlanTag::LEN {
+      return None;
+    }
+    let (tag, rest) = self.src.split_at(VlanTag::LEN);
+    self.src = rest;
+    Some(VlanTag::from_tci(
+      EtherType::from_u16(u16::from_be_bytes([tag[0], tag[1]])),
+      u16::from_be_bytes([tag[2], tag[3]]),
+    ))
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.src.len() / VlanTag::LEN;
+    (len, Some(len))
+  }
+}
+
+impl ExactSizeIterator for VlanTags<'_> {}
+
+impl FusedIterator for VlanTags<'_> {}
+
+/// Builds an Ethernet II or IEEE 802.3 header with up to two VLAN tags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EthernetHeaderBuilder {
+  destination: MacAddr,
+  source: MacAddr,
+  tags: [VlanTag; 2],
+  tag_count: usize,
+  type_or_length: TypeOrLength,
+}
+
+impl EthernetHeaderBuilder {
+  /// Creates an untagged header.
+  #[inline]
+  pub const fn new(destination: MacAddr, source: MacAddr, type_or_length: TypeOrLength) -> Self {
+    Self {
+      destination,
+      source,
+      tags: [VlanTag::new(0); 2],
+      tag_count: 0,
+      type_or_length,
+    }
+  }
+
+  /// Sets the destination address.
+  #[inline]
+  pub const fn with_destination(mut self, destination: MacAddr) -> Self {
+    self.destination = destination;
+    self
+  }
+
+  /// Sets the source address.
+  #[inline]
+  pub const fn with_source(mut self, source: MacAddr) -> Self {
+    self.source = source;
+    self
+  }
+
+  /// Sets the EtherType or 802.3 length field.
+  #[inline]
+  pub const fn with_type_or_length(mut self, type_or_length: TypeOrLength) -> Self {
+    self.type_or_length = type_or_length;
+    self
+  }
+
+  /// Appends a VLAN tag after the ones added before, so for a QinQ frame
+  /// add the service tag first.
+  ///
+  /// ## Panics
+  ///
+  /// Panics if the header already has two tags.
+  #[inline]
+  pub const fn with_vlan_tag(mut self, tag: VlanTag) -> Self {
+    assert!(self.tag_count < 2, "at most two VLAN tags are supported");
+    self.tags[self.tag_count] = tag;
+    self.tag_count += 1;
+    self
+  }
+
+  /// Returns the destination address.
+  #[inline]
+  pub const fn destination(&self) -> MacAddr {
+    self.destination
+  }
+
+  /// Returns the source address.
+  #[inline]
+  pub const fn source(&self) -> MacAddr {
+    self.source
+  }
+
+  /// Returns the VLAN tags, outermost first.
+  #[inline]
+  pub fn vlan_tags(&self) -> &[VlanTag] {
+    &self.tags[..self.tag_count]
+  }
+
+  /// Returns the EtherType or 802.3 length field.
+  #[inline]
+  pub const fn type_or_length(&self) -> TypeOrLength {
+    self.type_or_length
+  }
+
+  /// Returns the length of the header including its VLAN tags.
+  #[inline]
+  pub const fn header_len(&self) -> usize {
+    EthernetHeader::MIN_LEN + self.tag_count * VlanTag::LEN
+  }
+
+  /// Writes the header to the start of `buf`, returning its length.
+  ///
+  /// Fails if the EtherType/length field is neither a length nor an
+  /// EtherType, e.g. `Length(2000)` or `EtherType(Other(0x0100))`, as
+  /// [`EthernetHeader::parse`] would reject the header.
+  pub fn write(&self, buf: &mut [u8]) -> Result<usize, EthernetError> {
+    let value = self.type_or_length.to_u16();
+    if TypeOrLength::from_u16(value) != Some(self.type_or_length) {
+      return Err(EthernetError::InvalidTypeOrLength(value));
+    }
+    let len = self.header_len();
+    if buf.len() < len {
+      return Err(EthernetError::TooShort {
+        expected: len,
+        actual: buf.len(),
+      });
+    }
+
+    buf[..6].copy_from_slice(self.destination.as_bytes());
+    buf[6..12].copy_from_slice(self.source.as_bytes());
+    for (tag, dst) in self
+      .vlan_tags()
+      .iter()
+      .zip(buf[12..].chunks_exact_mut(VlanTag::LEN))
+    {
+      tag.write(dst);
+    }
+    buf[len - 2..len].copy_from_slice(&value.to_be_bytes());
+    Ok(len)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const DST: MacAddr = MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]);
+  const SRC: MacAddr = MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x02]);
+
+  #[test]
+  fn untagged() {
+    let frame = [
+      0x00, 0x00, 0x5e, 0x00, 0x53, 0x01, 0x00, 0x00, 0x5e, 0x00, 0x53, 0x02, 0x86, 0xdd, 0x60,
+    ];
+    let header = EthernetHeader::parse(&frame).unwrap();
+    assert_eq!(*header.destination(), DST);
+    assert_eq!(*header.source(), SRC);
+    assert_eq!(header.vlan_tags().len(), 0);
+    assert_eq!(header.ether_type(), Some(EtherType::Ipv6));
+    assert_eq!(header.payload(), [0x60]);
+
+    let mut buf = [0u8; 14];
+    let builder = EthernetHeaderBuilder::new(DST, SRC, TypeOrLength::EtherType(EtherType::Ipv6));
+    assert_eq!(builder.write(&mut buf), Ok(14));
+    assert_eq!(buf, frame[..14]);
+    assert_eq!(header.to_builder(), Some(builder));
+  }
+
+  #[test]
+  fn qinq() {
+    let builder = EthernetHeaderBuilder::new(DST, SRC, TypeOrLength::EtherType(EtherType::Ipv4))
+      .with_vlan_tag(VlanTag::service(300).with_pcp(7))
+      .with_vlan_tag(VlanTag::new(42).with_dei(true));
+    let mut buf = [0u8; 22];
+    assert_eq!(builder.write(&mut buf), Ok(22));
+    assert_eq!(
+      buf[12..],
+      [0x88, 0xa8, 0xe1, 0x2c, 0x81, 0x00, 0x10, 0x2a, 0x08, 0x00]
+    );
+
+    let header = EthernetHeader::parse(&buf).unwrap();
+    assert_eq!(header.header_len(), 22);
+    let tags: [VlanTag; 2] = [
+      header.vlan_tags().next().unwrap(),
+      header.vlan_tags().nth(1).unwrap(),
+    ];
+    assert_eq!(tags, builder.vlan_tags());
+    assert_eq!(
+      (tags[0].pcp(), tags[0].dei(), tags[0].vid()),
+      (7, false, 300)
+    );
+    assert_eq!((tags[1].pcp(), tags[1].dei(), tags[1].vid()), (0, true, 42));
+    assert!(header.payload().is_empty());
+    assert_eq!(header.to_builder(), Some(builder));
+  }
+
+  #[test]
+  fn deep_tag_stack() {
+    let mut frame = [0u8; 26];
+    for tag in frame[12..24].chunks_exact_mut(4) {
+      tag.copy_from_slice(&[0x81, 0x00, 0x00, 0x01]);
+    }
+    frame[24..].copy_from_slice(&[0x08, 0x06]);
+    let header = EthernetHeader::parse(&frame).unwrap();
+    assert_eq!(header.vlan_tags().len(), 3);
+    assert_eq!(header.to_builder(), None);
+  }
+
+  #[test]
+  fn ieee802_3_length() {
+    let mut frame = [0u8; 60];
+    frame[12..14].copy_from_slice(&46u16.to_be_bytes());
+    let header = EthernetHeader::parse(&frame).unwrap();
+    assert_eq!(header.type_or_length(), TypeOrLength::Length(46));
+    assert_eq!(header.ether_type(), None);
+  }
+
+  #[test]
+  fn errors() {
+    assert_eq!(
+      EthernetHeader::parse(&[0; 13]),
+      Err(EthernetError::TooShort {
+        expected: 14,
+        actual: 13
+      })
+    );
+    let mut frame = [0u8; 16];
+    frame[12..14].copy_from_slice(&[0x81, 0x00]);
+    assert_eq!(
+      EthernetHeader::parse(&frame),
+      Err(EthernetError::TooShort {
+        expected: 18,
+        actual: 16
+      })
+    );
+    frame[12..14].copy_from_slice(&0x05DDu16.to_be_bytes());
+    assert_eq!(
+      EthernetHeader::parse(&frame),
+      Err(EthernetError::InvalidTypeOrLength(0x05DD))
+    );
+
+    let builder =
+      EthernetHeaderBuilder::new(DST, SRC, TypeOrLength::Length(0)).with_vlan_tag(VlanTag::new(1));
+    assert_eq!(
+      builder.write(&mut [0; 17]),
+      Err(EthernetError::TooShort {
+        expected: 18,
+        actual: 17
+      })
+    );
+  }
+
+  #[test]
+  fn invalid_type_or_length() {
+    let mut buf = [0u8; 14];
+    for (tol, value) in [
+      (TypeOrLength::Length(2000), 2000),
+      (TypeOrLength::EtherType(EtherType::Other(0x0100)), 0x0100),
+      (TypeOrLength::EtherType(EtherType::Other(0x05DD)), 0x05DD),
+    ] {
+      assert_eq!(
+        EthernetHeaderBuilder::new(DST, SRC, tol).write(&mut buf),
+        Err(EthernetError::InvalidTypeOrLength(value))
+      );
+    }
+
+    let builder =
+      EthernetHeaderBuilder::new(DST, SRC, TypeOrLength::EtherType(EtherType::Other(0x0800)));
+    assert_eq!(builder.write(&mut buf), Ok(14));
+    let header = EthernetHeader::parse(&buf).unwrap();
+    assert_eq!(header.to_builder(), Some(builder));
+    assert_eq!(header.ether_type(), Some(EtherType::Ipv4));
+  }
+
+  #[test]
+  fn ether_type_values() {
+    assert_eq!(EtherType::from(0x0806), EtherType::Arp);
+    assert_eq!(EtherType::from(0x1234), EtherType::Other(0x1234));
+    assert_eq!(u16::from(EtherType::Lldp), 0x88CC);
+    assert!(EtherType::ServiceVlan.is_vlan_tpid());
+    assert!(EtherType::Other(0x8100).is_vlan_tpid());
+    assert!(!EtherType::Ipv4.is_vlan_tpid());
+
+    // Non-canonical `Other` values compare, order and hash by value.
+    assert_eq!(EtherType::Other(0x0800), EtherType::Ipv4);
+    assert!(EtherType::Other(0x0801) > EtherType::Ipv4);
+    // FNV-1a, as `DefaultHasher` is not available with only `alloc`.
+    struct Fnv(u64);
+    impl core::hash::Hasher for Fnv {
+      fn finish(&self) -> u64 {
+        self.0
+      }
+
+      fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+          self.0 = (self.0 ^ b as u64).wrapping_mul(0x100_0000_01b3);
+        }
+      }
+    }
+    let hash = |ty: EtherType| {
+      use core::hash::{Hash, Hasher};
+      let mut hasher = Fnv(0xcbf2_9ce4_8422_2325);
+      ty.hash(&mut hasher);
+      hasher.finish()
+    };
+    assert_eq!(hash(EtherType::Other(0x86DD)), hash(EtherType::Ipv6));
+  }
+
+  #[test]
+  #[should_panic]
+  fn vid_out_of_range() {
+    let _ = VlanTag::new(4096);
+  }
+}
@@ -1,14 +1,48 @@
+use crate::{AddrCipher, Pseudonymizer};
+
 addr_ty!(
   /// Represents a physical EUI-64 format address.
+  ///
+  /// `Display` prints the colon-separated form (`02:00:5e:10:00:00:00:01`),
+  /// and `{:#}` the IEEE hyphen-separated form (`02-00-5e-10-00-00-00-01`).
+  /// Width, fill and alignment are honored, e.g. `{:>30}`.
   Eui64Addr[8]
 );
 
+ieee802_addr_ty!(Eui64Addr);
+
+impl Eui64Addr {
+  /// Maps the address to its pseudonym under `pseudonymizer`.
+  ///
+  /// See [`Pseudonymizer`] for what is kept from the original address.
+  #[inline]
+  pub const fn pseudonymize(&self, pseudonymizer: &Pseudonymizer) -> Self {
+    Self(pseudonymizer.pseudonymize(&self.0))
+  }
+
+  /// Encrypts the address under `cipher`, keeping its I/G and U/L bits.
+  ///
+  /// See [`AddrCipher`] for details. [`decrypt`](Self::decrypt) with the same
+  /// cipher restores the original address.
+  #[inline]
+  pub const fn encrypt(&self, cipher: &AddrCipher) -> Self {
+    Self(cipher.encrypt(&self.0))
+  }
+
+  /// Decrypts an address produced by [`encrypt`](Self::encrypt) under the
+  /// same `cipher`.
+  #[inline]
+  pub const fn decrypt(&self, cipher: &AddrCipher) -> Self {
+    Self(cipher.decrypt(&self.0))
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::{ParseError, TestCase};
+  use crate::{Notation, ParseError, TestCase};
 
-  use std::{string::ToString, vec, vec::Vec};
+  use std::{format, string::ToString, vec, vec::Vec};
 
   const EUI64_ADDRESS_SIZE: usize = 8;
 
@@ -35,23 +69,32 @@ mod tests {
         output: Some(vec![0xab, 0xcd, 0xef, 0xab, 0xcd, 0xef, 0xab, 0xcd]),
         err: None,
       },
+      TestCase {
+        input: "02005e1000000001",
+        output: Some(vec![0x02, 0x00, 0x5e, 0x10, 0x00, 0x00, 0x00, 0x01]),
+        err: None,
+      },
+      TestCase {
+        input: "0x02005e1000000001",
+        output: Some(vec![0x02, 0x00, 0x5e, 0x10, 0x00, 0x00, 0x00, 0x01]),
+        err: None,
+      },
       TestCase {
         input: "0200-5e10.0000.0001",
         output: None,
-        err: Some(ParseError::UnexpectedSeparator {
-          expected: b'.',
-          actual: b'-',
-        }),
+        err: Some(
+          ParseError::unexpected_separator(b'.', b'-', 4).with_notation(Some(Notation::Dot)),
+        ),
       },
       TestCase {
         input: "xx00.5e10.0000.0001",
         output: None,
-        err: Some(ParseError::InvalidHexDigit([b'x', b'x'])),
+        err: Some(ParseError::invalid_hex_digit(b'x', 0).with_notation(Some(Notation::Dot))),
       },
       TestCase {
         input: "00xx.5e10.0000.0001",
         output: None,
-        err: Some(ParseError::InvalidHexDigit([b'x', b'x'])),
+        err: Some(ParseError::invalid_hex_digit(b'x', 2).with_notation(Some(Notation::Dot))),
       },
     ]
   }
@@ -137,6 +180,41 @@ mod tests {
     let dashed_str = core::str::from_utf8(&dashed).unwrap();
     assert_eq!(dashed_str, "02-00-5e-10-00-00-00-01");
     assert_eq!(addr.to_hyphen_separated(), "02-00-5e-10-00-00-00-01");
+
+    assert_eq!(addr.to_upper_colon_separated(), "02:00:5E:10:00:00:00:01");
+    assert_eq!(addr.to_upper_hyphen_separated(), "02-00-5E-10-00-00-00-01");
+    assert_eq!(addr.to_upper_dot_separated(), "0200.5E10.0000.0001");
+    assert_eq!(format!("{addr:X}"), "02:00:5E:10:00:00:00:01");
+    assert_eq!(format!("{addr:#x}"), "0x02005e1000000001");
+    assert_eq!(addr.to_bare_hex(), "02005e1000000001");
+    assert_eq!(format!("{addr:#}"), "02-00-5e-10-00-00-00-01");
+    assert_eq!(format!("{addr:>25}"), "  02:00:5e:10:00:00:00:01");
+  }
+
+  #[test]
+  fn pseudonymize() {
+    let addr = Eui64Addr::try_from("02:00:5e:10:00:00:00:01").unwrap();
+    let p = Pseudonymizer::new(*b"0123456789abcdef");
+    assert_eq!(addr.pseudonymize(&p).to_string(), "6e:62:16:34:97:37:1a:45");
+
+    let kept = addr.pseudonymize(&p.with_keep_oui(true));
+    assert_eq!(&kept.octets()[..3], &addr.octets()[..3]);
+    assert!(kept.is_local() && kept.is_unicast());
+  }
+
+  #[test]
+  fn encrypt() {
+    let addr = Eui64Addr::try_from("02:00:5e:10:00:00:00:01").unwrap();
+    let cipher = AddrCipher::new(*b"0123456789abcdef");
+
+    let token = addr.encrypt(&cipher);
+    assert_eq!(token.to_string(), "06:c6:ae:c2:2e:f2:73:6e");
+    assert_eq!(token.decrypt(&cipher), addr);
+
+    let cipher = cipher.with_keep_oui(true);
+    let token = addr.encrypt(&cipher);
+    assert_eq!(&token.octets()[..3], &addr.octets()[..3]);
+    assert_eq!(token.decrypt(&cipher), addr);
   }
 
   #[cfg(feature = "serde")]
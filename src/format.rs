@@ -0,0 +1,341 @@
+use core::fmt::{self, Write};
+
+/// Letter case of the hex digits `a`-`f`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Case {
+  /// Lowercase digits, e.g. `5e`.
+  #[default]
+  Lower,
+  /// Uppercase digits, e.g. `5E`.
+  Upper,
+}
+
+impl Case {
+  #[inline]
+  const fn digits(&self) -> &'static [u8; 16] {
+    match self {
+      Self::Lower => &crate::__private::HEX_DIGITS,
+      Self::Upper => &crate::__private::UPPER_HEX_DIGITS,
+    }
+  }
+}
+
+/// Describes how an address is printed: separator, group size, case and
+/// prefix.
+///
+/// Use it through `display_with`, which returns a
+/// [`Display`](core::fmt::Display) adapter, or `format_into`, which writes
+/// into a caller-provided buffer. Neither allocates.
+///
+/// ## Example
+///
+/// ```rust
+/// use hardware_address::{Case, FormatOptions, MacAddr};
+///
+/// let addr: MacAddr = "00:11:22:33:44:55".parse().unwrap();
+///
+/// assert_eq!(addr.display_with(FormatOptions::DOT).to_string(), "0011.2233.4455");
+///
+/// let opts = FormatOptions::new()
+///   .with_separator(Some('-'))
+///   .with_group_size(3)
+///   .with_case(Case::Upper);
+/// assert_eq!(addr.display_with(opts).to_string(), "001122-334455");
+///
+/// let mut buf = [0u8; 32];
+/// assert_eq!(addr.format_into(FormatOptions::PREFIXED_HEX, &mut buf), Some("0x001122334455"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FormatOptions {
+  separator: Option<char>,
+  group_size: usize,
+  case: Case,
+  prefix: Option<&'static str>,
+}
+
+impl Default for FormatOptions {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl FormatOptions {
+  /// Colon-separated octets, e.g. `00:11:22:33:44:55`.
+  pub const COLON: Self = Self::new();
+
+  /// Hyphen-separated octets, e.g. `00-11-22-33-44-55`.
+  pub const HYPHEN: Self = Self::new().with_separator(Some('-'));
+
+  /// Dot-separated groups of two octets, e.g. `0011.2233.4455`.
+  pub const DOT: Self = Self::new().with_separator(Some('.')).with_group_size(2);
+
+  /// Space-separated octets, e.g. `00 11 22 33 44 55`.
+  pub const SPACE: Self = Self::new().with_separator(Some(' '));
+
+  /// Unseparated hex digits, e.g. `001122334455`.
+  pub const BARE_HEX: Self = Self::new().with_separator(None);
+
+  /// Unseparated hex digits with a `0x` prefix, e.g. `0x001122334455`.
+  pub const PREFIXED_HEX: Self = Self::BARE_HEX.with_prefix(Some("0x"));
+
+  /// Creates the default options: lowercase, colon-separated octets without
+  /// a prefix.
+  #[inline]
+  pub const fn new() -> Self {
+    Self {
+      separator: Some(':'),
+      group_size: 1,
+      case: Case::Lower,
+      prefix: None,
+    }
+  }
+
+  /// Sets the separator printed between groups, or `None` for no separator.
+  ///
+  /// Default is `Some(':')`.
+  #[inline]
+  pub const fn with_separator(mut self, separator: Option<char>) -> Self {
+    self.separator = separator;
+    self
+  }
+
+  /// Sets how many octets are printed between two separators.
+  ///
+  /// `0` puts the whole address in a single group. If the address length
+  /// is not a multiple of the group size the last group is shorter.
+  ///
+  /// Default is `1`.
+  #[inline]
+  pub const fn with_group_size(mut self, octets: usize) -> Self {
+    self.group_size = octets;
+    self
+  }
+
+  /// Sets the case of the hex digits.
+  ///
+  /// Default is [`Case::Lower`].
+  #[inline]
+  pub const fn with_case(mut self, case: Case) -> Self {
+    self.case = case;
+    self
+  }
+
+  /// Sets a prefix printed before the address, e.g. `0x`.
+  ///
+  /// Default is `None`.
+  #[inline]
+  pub const fn with_prefix(mut self, prefix: Option<&'static str>) -> Self {
+    self.prefix = prefix;
+    self
+  }
+
+  /// Returns the separator printed between groups.
+  #[inline]
+  pub const fn separator(&self) -> Option<char> {
+    self.separator
+  }
+
+  /// Returns how many octets are printed between two separators.
+  #[inline]
+  pub const fn group_size(&self) -> usize {
+    self.group_size
+  }
+
+  /// Returns the case of the hex digits.
+  #[inline]
+  pub const fn case(&self) -> Case {
+    self.case
+  }
+
+  /// Returns the prefix printed before the address.
+  #[inline]
+  pub const fn prefix(&self) -> Option<&'static str> {
+    self.prefix
+  }
+
+  /// Returns the number of bytes needed to format an address of `octets`
+  /// octets with these options.
+  pub const fn formatted_len(&self, octets: usize) -> usize {
+    let prefix = match self.prefix {
+      Some(p) => p.len(),
+      None => 0,
+    };
+    let separators = match self.separator {
+      Some(sep) if octets > 0 => sep.len_utf8() * (self.groups(octets) - 1),
+      _ => 0,
+    };
+    prefix + octets * 2 + separators
+  }
+
+  #[inline]
+  const fn groups(&self, octets: usize) -> usize {
+    match self.group_size {
+      0 => 1,
+      // Rounds up without overflowing for group sizes near `usize::MAX`.
+      size => octets / size + (octets % size != 0) as usize,
+    }
+  }
+
+  fn write<W: Write + ?Sized>(&self, octets: &[u8], w: &mut W) -> fmt::Result {
+    if let Some(prefix) = self.prefix {
+      w.write_str(prefix)?;
+    }
+
+    let digits = self.case.digits();
+    for (i, &b) in octets.iter().enumerate() {
+      if i > 0 && self.group_size != 0 && i % self.group_size == 0 {
+        if let Some(sep) = self.separator {
+          w.write_char(sep)?;
+        }
+      }
+      let pair = [digits[(b >> 4) as usize], digits[(b & 0xF) as usize]];
+      // SAFETY: hex digits are ASCII.
+      w.write_str(unsafe { core::str::from_utf8_unchecked(&pair) })?;
+    }
+    Ok(())
+  }
+
+  /// Formats `octets` into `buf` and returns the written part of `buf` as
+  /// a `str`, or `None` if `buf` is shorter than
+  /// [`formatted_len`](Self::formatted_len).
+  pub fn format_into<'b>(&self, octets: &[u8], buf: &'b mut [u8]) -> Option<&'b str> {
+    if buf.len() < self.formatted_len(octets.len()) {
+      return None;
+    }
+
+    let mut cursor = Cursor { buf, pos: 0 };
+    // Cannot fail: the buffer was checked to be large enough.
+    let _ = self.write(octets, &mut cursor);
+    let pos = cursor.pos;
+    // SAFETY: only `&str`s and `char`s were written, so the bytes are UTF-8.
+    Some(unsafe { core::str::from_utf8_unchecked(&cursor.buf[..pos]) })
+  }
+}
+
+/// A [`Display`](core::fmt::Display) adapter printing an address with the
+/// given [`FormatOptions`].
+///
+/// Created by the `display_with` method of the address types, for example
+/// [`MacAddr::display_with`](crate::MacAddr::display_with).
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayWith<'a> {
+  octets: &'a [u8],
+  options: FormatOptions,
+}
+
+impl<'a> DisplayWith<'a> {
+  /// Creates an adapter printing `octets` with `options`.
+  #[inline]
+  pub const fn new(octets: &'a [u8], options: FormatOptions) -> Self {
+    Self { octets, options }
+  }
+
+  /// Returns the options used by this adapter.
+  #[inline]
+  pub const fn options(&self) -> FormatOptions {
+    self.options
+  }
+}
+
+impl fmt::Display for DisplayWith<'_> {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.options.write(self.octets, f)
+  }
+}
+
+struct Cursor<'b> {
+  buf: &'b mut [u8],
+  pos: usize,
+}
+
+impl Write for Cursor<'_> {
+  #[inline]
+  fn write_str(&mut self, s: &str) -> fmt::Result {
+    let end = self.pos + s.len();
+    if end > self.buf.len() {
+      return Err(fmt::Error);
+    }
+    self.buf[self.pos..end].copy_from_slice(s.as_bytes());
+    self.pos = end;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{Eui64Addr, MacAddr};
+
+  use std::string::ToString;
+
+  const MAC: MacAddr = MacAddr::from_raw([0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e]);
+
+  #[test]
+  fn presets() {
+    let cases = [
+      (FormatOptions::COLON, "00:1a:2b:3c:4d:5e"),
+      (FormatOptions::HYPHEN, "00-1a-2b-3c-4d-5e"),
+      (FormatOptions::DOT, "001a.2b3c.4d5e"),
+      (FormatOptions::SPACE, "00 1a 2b 3c 4d 5e"),
+      (FormatOptions::BARE_HEX, "001a2b3c4d5e"),
+      (FormatOptions::PREFIXED_HEX, "0x001a2b3c4d5e"),
+    ];
+    let mut buf = [0u8; 32];
+    for (opts, expected) in cases {
+      assert_eq!(MAC.display_with(opts).to_string(), expected);
+      assert_eq!(MAC.format_into(opts, &mut buf), Some(expected));
+      assert_eq!(opts.formatted_len(MacAddr::SIZE), expected.len());
+    }
+    assert_eq!(FormatOptions::default(), FormatOptions::COLON);
+  }
+
+  #[test]
+  fn custom() {
+    let opts = FormatOptions::new()
+      .with_separator(Some('-'))
+      .with_group_size(3)
+      .with_case(Case::Upper);
+    assert_eq!(opts.separator(), Some('-'));
+    assert_eq!(opts.group_size(), 3);
+    assert_eq!(opts.case(), Case::Upper);
+    assert_eq!(opts.prefix(), None);
+    assert_eq!(MAC.display_with(opts).to_string(), "001A2B-3C4D5E");
+
+    // Uneven trailing group and a multi-byte separator.
+    let opts = FormatOptions::new()
+      .with_separator(Some('·'))
+      .with_group_size(4);
+    let eui = Eui64Addr::from_raw([0x02, 0x00, 0x5e, 0x10, 0x00, 0x00, 0x00, 0x01]);
+    assert_eq!(MAC.display_with(opts).to_string(), "001a2b3c·4d5e");
+    assert_eq!(eui.display_with(opts).to_string(), "02005e10·00000001");
+    let mut buf = [0u8; 32];
+    assert_eq!(MAC.format_into(opts, &mut buf), Some("001a2b3c·4d5e"));
+
+    // Group size 0 means a single group.
+    let opts = FormatOptions::new()
+      .with_group_size(0)
+      .with_prefix(Some("mac="));
+    assert_eq!(MAC.display_with(opts).to_string(), "mac=001a2b3c4d5e");
+    assert_eq!(opts.formatted_len(MacAddr::SIZE), 16);
+
+    // A group larger than the address is a single group.
+    let opts = FormatOptions::new().with_group_size(usize::MAX);
+    assert_eq!(opts.formatted_len(MacAddr::SIZE), 12);
+    assert_eq!(opts.formatted_len(0), 0);
+    let mut buf = [0u8; 12];
+    assert_eq!(MAC.format_into(opts, &mut buf), Some("001a2b3c4d5e"));
+    assert_eq!(MAC.display_with(opts).to_string(), "001a2b3c4d5e");
+  }
+
+  #[test]
+  fn format_into_too_small() {
+    let mut buf = [0u8; 16];
+    assert_eq!(MAC.format_into(FormatOptions::COLON, &mut buf), None);
+    assert_eq!(
+      MAC.format_into(FormatOptions::COLON, &mut [0u8; 17]),
+      Some("00:1a:2b:3c:4d:5e")
+    );
+  }
+}
@@ -0,0 +1,64 @@
+//! Hex helpers shared by the protocol and host discovery modules.
+
+use core::fmt;
+
+use crate::__private::{HEX_DIGITS, HEX_VAL};
+
+/// An error returned by [`decode_hex`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HexError {
+  /// The text is malformed at this offset.
+  InvalidText(usize),
+  /// The text holds about this many octets, more than fit the buffer.
+  TooLong(usize),
+}
+
+/// Decodes colon-separated or bare hex octets, as DHCP servers print DUIDs
+/// and client identifiers and the kernel and iproute2 print link-layer
+/// addresses, into `buf`, returning the number of octets.
+pub(crate) fn decode_hex(src: &str, buf: &mut [u8]) -> Result<usize, HexError> {
+  let src = src.as_bytes();
+  let colon = src.contains(&b':');
+  let step = if colon { 3 } else { 2 };
+  let mut len = 0;
+  let mut at = 0;
+  while at < src.len() {
+    if len == buf.len() {
+      return Err(HexError::TooLong((src.len() + step - 1) / step));
+    }
+    if colon && at > 0 {
+      if src[at] != b':' {
+        return Err(HexError::InvalidText(at));
+      }
+      at += 1;
+    }
+    let (hi, lo) = match (src.get(at), src.get(at + 1)) {
+      (Some(&hi), Some(&lo)) => (HEX_VAL[hi as usize], HEX_VAL[lo as usize]),
+      _ => return Err(HexError::InvalidText(src.len())),
+    };
+    if hi == 0xFF {
+      return Err(HexError::InvalidText(at));
+    }
+    if lo == 0xFF {
+      return Err(HexError::InvalidText(at + 1));
+    }
+    buf[len] = hi << 4 | lo;
+    len += 1;
+    at += 2;
+  }
+  Ok(len)
+}
+
+/// Writes `bytes` as colon-separated lowercase hex octets.
+pub(crate) fn write_hex(bytes: &[u8], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+  use fmt::Write;
+
+  for (i, b) in bytes.iter().enumerate() {
+    if i > 0 {
+      f.write_char(':')?;
+    }
+    f.write_char(HEX_DIGITS[(b >> 4) as usize] as char)?;
+    f.write_char(HEX_DIGITS[(b & 0xF) as usize] as char)?;
+  }
+  Ok(())
+}
@@ -1,5 +1,12 @@
 addr_ty!(
   /// Represents a physical 20-octet InfiniBand format address.
+  ///
+  /// `Display` prints the colon-separated form, and `{:#}` the
+  /// hyphen-separated form. Width, fill and alignment are honored.
+  ///
+  /// The first octet holds IP over InfiniBand flags rather than the IEEE 802
+  /// I/G and U/L bits, so unlike `MacAddr` this type has no `is_unicast` or
+  /// `random_local_unicast` style methods.
   InfiniBandAddr[20]
 );
 
@@ -31,6 +38,14 @@ mod tests {
         ]),
         err: None,
       },
+      TestCase {
+        input: "00000000fe8000000000000002005e1000000001",
+        output: Some(vec![
+          0x00, 0x00, 0x00, 0x00, 0xfe, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x5e,
+          0x10, 0x00, 0x00, 0x00, 0x01,
+        ]),
+        err: None,
+      },
       TestCase {
         input: "0000.0000.fe80.0000.0000.0000.0200.5e10.0000.0001",
         output: Some(vec![
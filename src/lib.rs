@@ -32,6 +32,9 @@ macro_rules! addr_ty {
         #[cfg(feature = "wasm-bindgen")]
         use $crate::__private::wasm_bindgen as __wasm_bindgen;
 
+        #[cfg(feature = "zerocopy")]
+        use $crate::__private::zerocopy as __zerocopy;
+
         #[doc = "Represents an error that occurred while parsing `" $name "`."]
         pub type [< Parse $name Error >] = $crate::ParseError<$n>;
 
@@ -44,6 +47,11 @@ macro_rules! addr_ty {
         // be passed as arguments to `#[pyfunction]` / `#[pymethods]`.
         #[cfg_attr(feature = "pyo3", $crate::__private::pyo3::pyclass(crate = "__pyo3", from_py_object))]
         #[cfg_attr(feature = "wasm-bindgen", $crate::__private::wasm_bindgen::prelude::wasm_bindgen(wasm_bindgen = __wasm_bindgen))]
+        #[cfg_attr(
+          feature = "zerocopy",
+          derive(__zerocopy::FromBytes, __zerocopy::IntoBytes, __zerocopy::KnownLayout, __zerocopy::Unaligned, __zerocopy::Immutable),
+          zerocopy(crate = "self::__zerocopy")
+        )]
         #[repr(transparent)]
         pub struct $name(pub(crate) [::core::primitive::u8; $n]);
       }
@@ -80,6 +88,39 @@ macro_rules! addr_ty {
           &self.0
         }
 
+        /// Views `src` as an address without copying.
+        ///
+        /// Fails if `src` is not exactly [`SIZE`](Self::SIZE) bytes long.
+        /// Since the address has no alignment requirement, any such slice
+        /// of a packet buffer can be borrowed.
+        #[inline]
+        pub const fn ref_from_bytes(src: &[::core::primitive::u8]) -> ::core::result::Result<&Self, $crate::SliceLengthError> {
+          if src.len() != $n {
+            return ::core::result::Result::Err($crate::SliceLengthError::exact($n, src.len()));
+          }
+          // SAFETY: `Self` is a `#[repr(transparent)]` wrapper around
+          // `[u8; $n]`, which has alignment 1, and `src` is exactly `$n`
+          // bytes long.
+          ::core::result::Result::Ok(unsafe { &*(src.as_ptr() as *const Self) })
+        }
+
+        /// Views `src` as a slice of consecutive addresses without copying.
+        ///
+        /// Fails if the length of `src` is not a multiple of
+        /// [`SIZE`](Self::SIZE). An empty `src` gives an empty slice.
+        #[inline]
+        pub const fn slice_from_bytes(src: &[::core::primitive::u8]) -> ::core::result::Result<&[Self], $crate::SliceLengthError> {
+          if src.len() % $n != 0 {
+            return ::core::result::Result::Err($crate::SliceLengthError::multiple_of($n, src.len()));
+          }
+          // SAFETY: `Self` is a `#[repr(transparent)]` wrapper around
+          // `[u8; $n]`, which has alignment 1 and no padding, and `src`
+          // holds exactly `src.len() / $n` of them.
+          ::core::result::Result::Ok(unsafe {
+            ::core::slice::from_raw_parts(src.as_ptr() as *const Self, src.len() / $n)
+          })
+        }
+
         /// Returns the octets of the address.
         #[inline]
         pub const fn octets(&self) -> [::core::primitive::u8; $n] {
@@ -92,58 +133,129 @@ macro_rules! addr_ty {
         /// by using [`core::str::from_utf8(&array).unwrap( )`](core::str::from_utf8).
         #[inline]
         pub const fn to_colon_separated_array(&self) -> [::core::primitive::u8; $n * 3 - 1] {
-          let mut buf = [0u8; $n * 3 - 1];
+          self.__separated_array(b':', &$crate::__private::HEX_DIGITS)
+        }
+
+        /// Returns an array contains a hyphen formatted address.
+        ///
+        /// The returned array can be used to directly convert to `str`
+        /// by using [`core::str::from_utf8(&array).unwrap( )`](core::str::from_utf8).
+        #[inline]
+        pub const fn to_hyphen_separated_array(&self) -> [::core::primitive::u8; $n * 3 - 1] {
+          self.__separated_array(b'-', &$crate::__private::HEX_DIGITS)
+        }
+
+        /// Returns an array contains a dot formatted address.
+        ///
+        /// The returned array can be used to directly convert to `str`
+        /// by using [`core::str::from_utf8(&array).unwrap( )`](core::str::from_utf8).
+        #[inline]
+        pub const fn to_dot_separated_array(&self) -> [::core::primitive::u8; $n * 2 + ($n / 2 - 1)] {
+          self.__dot_separated_array(&$crate::__private::HEX_DIGITS)
+        }
+
+        /// Returns an array contains an uppercase colon formatted address,
+        /// e.g. `00:1A:2B:3C:4D:5E`.
+        ///
+        /// The returned array can be used to directly convert to `str`
+        /// by using [`core::str::from_utf8(&array).unwrap( )`](core::str::from_utf8).
+        #[inline]
+        pub const fn to_upper_colon_separated_array(&self) -> [::core::primitive::u8; $n * 3 - 1] {
+          self.__separated_array(b':', &$crate::__private::UPPER_HEX_DIGITS)
+        }
+
+        /// Returns an array contains an uppercase hyphen formatted address,
+        /// e.g. `00-1A-2B-3C-4D-5E`.
+        ///
+        /// The returned array can be used to directly convert to `str`
+        /// by using [`core::str::from_utf8(&array).unwrap( )`](core::str::from_utf8).
+        #[inline]
+        pub const fn to_upper_hyphen_separated_array(&self) -> [::core::primitive::u8; $n * 3 - 1] {
+          self.__separated_array(b'-', &$crate::__private::UPPER_HEX_DIGITS)
+        }
+
+        /// Returns an array contains an uppercase dot formatted address,
+        /// e.g. `001A.2B3C.4D5E`.
+        ///
+        /// The returned array can be used to directly convert to `str`
+        /// by using [`core::str::from_utf8(&array).unwrap( )`](core::str::from_utf8).
+        #[inline]
+        pub const fn to_upper_dot_separated_array(&self) -> [::core::primitive::u8; $n * 2 + ($n / 2 - 1)] {
+          self.__dot_separated_array(&$crate::__private::UPPER_HEX_DIGITS)
+        }
+
+        /// Returns an array contains the address as unseparated hex digits,
+        /// e.g. `001a2b3c4d5e`.
+        ///
+        /// The returned array can be used to directly convert to `str`
+        /// by using [`core::str::from_utf8(&array).unwrap( )`](core::str::from_utf8).
+        #[inline]
+        pub const fn to_bare_hex_array(&self) -> [::core::primitive::u8; $n * 2] {
+          self.__bare_hex_array(&$crate::__private::HEX_DIGITS)
+        }
+
+        /// Returns an array contains the address as unseparated uppercase hex
+        /// digits, e.g. `001A2B3C4D5E`.
+        ///
+        /// The returned array can be used to directly convert to `str`
+        /// by using [`core::str::from_utf8(&array).unwrap( )`](core::str::from_utf8).
+        #[inline]
+        pub const fn to_upper_bare_hex_array(&self) -> [::core::primitive::u8; $n * 2] {
+          self.__bare_hex_array(&$crate::__private::UPPER_HEX_DIGITS)
+        }
+
+        #[inline]
+        const fn __bare_hex_array(
+          &self,
+          digits: &[::core::primitive::u8; 16],
+        ) -> [::core::primitive::u8; $n * 2] {
+          let mut buf = [0u8; $n * 2];
           let mut i = 0;
 
           while i < $n {
-            if i > 0 {
-              buf[i * 3 - 1] = b':';
-            }
-
-            buf[i * 3] = $crate::__private::HEX_DIGITS[(self.0[i] >> 4) as ::core::primitive::usize];
-            buf[i * 3 + 1] = $crate::__private::HEX_DIGITS[(self.0[i] & 0xF) as ::core::primitive::usize];
+            buf[i * 2] = digits[(self.0[i] >> 4) as ::core::primitive::usize];
+            buf[i * 2 + 1] = digits[(self.0[i] & 0xF) as ::core::primitive::usize];
             i += 1;
           }
 
           buf
         }
 
-        /// Returns an array contains a hyphen formatted address.
-        ///
-        /// The returned array can be used to directly convert to `str`
-        /// by using [`core::str::from_utf8(&array).unwrap( )`](core::str::from_utf8).
         #[inline]
-        pub const fn to_hyphen_separated_array(&self) -> [::core::primitive::u8; $n * 3 - 1] {
+        const fn __separated_array(
+          &self,
+          sep: ::core::primitive::u8,
+          digits: &[::core::primitive::u8; 16],
+        ) -> [::core::primitive::u8; $n * 3 - 1] {
           let mut buf = [0u8; $n * 3 - 1];
           let mut i = 0;
 
           while i < $n {
             if i > 0 {
-              buf[i * 3 - 1] = b'-';
+              buf[i * 3 - 1] = sep;
             }
 
-            buf[i * 3] = $crate::__private::HEX_DIGITS[(self.0[i] >> 4) as ::core::primitive::usize];
-            buf[i * 3 + 1] = $crate::__private::HEX_DIGITS[(self.0[i] & 0xF) as ::core::primitive::usize];
+            buf[i * 3] = digits[(self.0[i] >> 4) as ::core::primitive::usize];
+            buf[i * 3 + 1] = digits[(self.0[i] & 0xF) as ::core::primitive::usize];
             i += 1;
           }
 
           buf
         }
 
-        /// Returns an array contains a dot formatted address.
-        ///
-        /// The returned array can be used to directly convert to `str`
-        /// by using [`core::str::from_utf8(&array).unwrap( )`](core::str::from_utf8).
         #[inline]
-        pub const fn to_dot_separated_array(&self) -> [::core::primitive::u8; $n * 2 + ($n / 2 - 1)] {
+        const fn __dot_separated_array(
+          &self,
+          digits: &[::core::primitive::u8; 16],
+        ) -> [::core::primitive::u8; $n * 2 + ($n / 2 - 1)] {
           let mut buf = [0u8; $n * 2 + ($n / 2 - 1)];
           let mut i = 0;
 
           while i < $n {
             // Convert first nibble to hex char
-            buf[i * 2 + i / 2] = $crate::__private::HEX_DIGITS[(self.0[i] >> 4) as ::core::primitive::usize];
+            buf[i * 2 + i / 2] = digits[(self.0[i] >> 4) as ::core::primitive::usize];
             // Convert second nibble to hex char
-            buf[i * 2 + 1 + i / 2] = $crate::__private::HEX_DIGITS[(self.0[i] & 0xF) as ::core::primitive::usize];
+            buf[i * 2 + 1 + i / 2] = digits[(self.0[i] & 0xF) as ::core::primitive::usize];
 
             // Add dot every 2 bytes except for the last group
             if i % 2 == 1 && i != $n - 1 {
@@ -155,6 +267,35 @@ macro_rules! addr_ty {
           buf
         }
 
+        /// Returns a [`Display`](::core::fmt::Display) adapter printing the
+        /// address with its trailing octets masked, e.g. `00:11:22:xx:xx:xx`.
+        ///
+        /// See [`Redacted`]($crate::Redacted) for the available options.
+        #[inline]
+        pub const fn redacted(&self) -> $crate::Redacted<{ $n * 3 - 1 }> {
+          $crate::Redacted::new(self.to_colon_separated_array())
+        }
+
+        /// Returns a [`Display`](::core::fmt::Display) adapter printing the
+        /// address with the given [`FormatOptions`]($crate::FormatOptions).
+        #[inline]
+        pub const fn display_with(&self, options: $crate::FormatOptions) -> $crate::DisplayWith<'_> {
+          $crate::DisplayWith::new(&self.0, options)
+        }
+
+        /// Formats the address with the given [`FormatOptions`]($crate::FormatOptions)
+        /// into `buf`, returning the written part of `buf` as a `str`, or `None`
+        /// if `buf` is shorter than
+        /// [`options.formatted_len(Self::SIZE)`]($crate::FormatOptions::formatted_len).
+        #[inline]
+        pub fn format_into<'b>(
+          &self,
+          options: $crate::FormatOptions,
+          buf: &'b mut [::core::primitive::u8],
+        ) -> ::core::option::Option<&'b ::core::primitive::str> {
+          options.format_into(&self.0, buf)
+        }
+
         /// Converts to colon-separated format string.
         #[cfg(any(feature = "alloc", feature = "std"))]
         #[cfg_attr(docsrs, doc(cfg(any(feature = "alloc", feature = "std"))))]
@@ -181,6 +322,169 @@ macro_rules! addr_ty {
           // SAFETY: The buffer is always valid UTF-8 as it only contains ASCII characters.
           unsafe { $crate::__private::ToString::to_string(::core::str::from_utf8_unchecked(&buf)) }
         }
+
+        /// Converts to unseparated hex digits string.
+        #[cfg(any(feature = "alloc", feature = "std"))]
+        #[cfg_attr(docsrs, doc(cfg(any(feature = "alloc", feature = "std"))))]
+        pub fn to_bare_hex(&self) -> $crate::__private::String {
+          let buf = self.to_bare_hex_array();
+          // SAFETY: The buffer is always valid UTF-8 as it only contains ASCII characters.
+          unsafe { $crate::__private::ToString::to_string(::core::str::from_utf8_unchecked(&buf)) }
+        }
+
+        /// Converts to uppercase colon-separated format string.
+        #[cfg(any(feature = "alloc", feature = "std"))]
+        #[cfg_attr(docsrs, doc(cfg(any(feature = "alloc", feature = "std"))))]
+        pub fn to_upper_colon_separated(&self) -> $crate::__private::String {
+          let buf = self.to_upper_colon_separated_array();
+          // SAFETY: The buffer is always valid UTF-8 as it only contains ASCII characters.
+          unsafe { $crate::__private::ToString::to_string(::core::str::from_utf8_unchecked(&buf)) }
+        }
+
+        /// Converts to uppercase hyphen-separated format string.
+        #[cfg(any(feature = "alloc", feature = "std"))]
+        #[cfg_attr(docsrs, doc(cfg(any(feature = "alloc", feature = "std"))))]
+        pub fn to_upper_hyphen_separated(&self) -> $crate::__private::String {
+          let buf = self.to_upper_hyphen_separated_array();
+          // SAFETY: The buffer is always valid UTF-8 as it only contains ASCII characters.
+          unsafe { $crate::__private::ToString::to_string(::core::str::from_utf8_unchecked(&buf)) }
+        }
+
+        /// Converts to uppercase dot-separated format string.
+        #[cfg(any(feature = "alloc", feature = "std"))]
+        #[cfg_attr(docsrs, doc(cfg(any(feature = "alloc", feature = "std"))))]
+        pub fn to_upper_dot_separated(&self) -> $crate::__private::String {
+          let buf = self.to_upper_dot_separated_array();
+          // SAFETY: The buffer is always valid UTF-8 as it only contains ASCII characters.
+          unsafe { $crate::__private::ToString::to_string(::core::str::from_utf8_unchecked(&buf)) }
+        }
+
+        /// Converts to unseparated uppercase hex digits string.
+        #[cfg(any(feature = "alloc", feature = "std"))]
+        #[cfg_attr(docsrs, doc(cfg(any(feature = "alloc", feature = "std"))))]
+        pub fn to_upper_bare_hex(&self) -> $crate::__private::String {
+          let buf = self.to_upper_bare_hex_array();
+          // SAFETY: The buffer is always valid UTF-8 as it only contains ASCII characters.
+          unsafe { $crate::__private::ToString::to_string(::core::str::from_utf8_unchecked(&buf)) }
+        }
+      }
+
+      impl $name {
+        /// Parses an address, tolerating unpadded octets, surrounding
+        /// whitespace and mixed separators.
+        ///
+        /// See [`parse_lenient`]($crate::parse_lenient) for the accepted
+        /// input. [`FromStr`](::core::str::FromStr) stays strict.
+        #[inline]
+        pub const fn parse_lenient(
+          src: &::core::primitive::str,
+        ) -> ::core::result::Result<Self, $crate::ParseError<$n>> {
+          match $crate::parse_lenient::<$n>(src.as_bytes()) {
+            ::core::result::Result::Ok(addr) => ::core::result::Result::Ok($name(addr)),
+            ::core::result::Result::Err(err) => ::core::result::Result::Err(err),
+          }
+        }
+      }
+
+      impl $name {
+        /// Parses an address, only accepting the notations and case allowed
+        /// by `options`.
+        ///
+        /// See [`parse_with`]($crate::parse_with).
+        #[inline]
+        pub const fn parse_with(
+          src: &::core::primitive::str,
+          options: $crate::ParseOptions,
+        ) -> ::core::result::Result<Self, $crate::ParseError<$n>> {
+          match $crate::parse_with::<$n>(src.as_bytes(), options) {
+            ::core::result::Result::Ok(addr) => ::core::result::Result::Ok($name(addr)),
+            ::core::result::Result::Err(err) => ::core::result::Result::Err(err),
+          }
+        }
+      }
+
+      impl $name {
+        /// Returns an iterator over the addresses found in `src`, with their
+        /// byte ranges.
+        ///
+        /// See [`AddrScanner`]($crate::AddrScanner) for the recognized
+        /// notations and word boundary rules.
+        #[inline]
+        pub fn find_iter(
+          src: &::core::primitive::str,
+        ) -> impl ::core::iter::Iterator<Item = (::core::ops::Range<::core::primitive::usize>, Self)> + '_
+        {
+          $crate::find_iter::<$n>(src).map(|(range, addr)| (range, $name(addr)))
+        }
+      }
+
+      impl $name {
+        /// Parses an address from ASCII bytes, without UTF-8 validation.
+        ///
+        /// Accepts the same input as [`FromStr`](::core::str::FromStr), see
+        /// [`parse`]($crate::parse).
+        #[inline]
+        pub const fn parse_ascii(
+          src: &[::core::primitive::u8],
+        ) -> ::core::result::Result<Self, $crate::ParseError<$n>> {
+          match $crate::parse::<$n>(src) {
+            ::core::result::Result::Ok(addr) => ::core::result::Result::Ok($name(addr)),
+            ::core::result::Result::Err(err) => ::core::result::Result::Err(err),
+          }
+        }
+
+        /// Parses an address at the start of `src` and returns it together
+        /// with the number of bytes it takes.
+        ///
+        /// See [`parse_prefix`]($crate::parse_prefix).
+        #[inline]
+        pub const fn parse_prefix(
+          src: &[::core::primitive::u8],
+        ) -> ::core::result::Result<(Self, ::core::primitive::usize), $crate::ParseError<$n>> {
+          match $crate::parse_prefix::<$n>(src) {
+            ::core::result::Result::Ok((addr, len)) => ::core::result::Result::Ok(($name(addr), len)),
+            ::core::result::Result::Err(err) => ::core::result::Result::Err(err),
+          }
+        }
+      }
+
+      impl $name {
+        /// Parses every string of `src` into the same position of `out`,
+        /// taking SIMD fast paths where the CPU supports them.
+        ///
+        /// See [`parse_many`]($crate::parse_many).
+        ///
+        /// ## Panics
+        ///
+        /// Panics if `src` and `out` have different lengths.
+        pub fn parse_many(
+          src: &[&::core::primitive::str],
+          out: &mut [Self],
+        ) -> ::core::result::Result<(), $crate::BatchParseError> {
+          ::core::assert_eq!(
+            src.len(),
+            out.len(),
+            "`src` and `out` must have the same length"
+          );
+          $crate::__private::parse_many_by::<$n, _>(src, |i, addr| out[i] = $name(addr))
+        }
+
+        /// Formats every address of `addrs` in the colon-separated form into
+        /// the same position of `out`.
+        ///
+        /// See [`format_many`]($crate::format_many).
+        ///
+        /// ## Panics
+        ///
+        /// Panics if `addrs` and `out` have different lengths.
+        pub fn format_many(addrs: &[Self], out: &mut [[::core::primitive::u8; $n * 3 - 1]]) {
+          ::core::assert_eq!(
+            addrs.len(),
+            out.len(),
+            "`addrs` and `out` must have the same length"
+          );
+          $crate::__private::format_many_by::<$n, { $n * 3 - 1 }, _>(addrs.len(), |i| addrs[i].0, out)
+        }
       }
 
       impl ::core::str::FromStr for $name {
@@ -243,6 +547,13 @@ macro_rules! addr_ty {
         }
       }
 
+      impl ::core::convert::AsMut<[::core::primitive::u8]> for $name {
+        #[inline]
+        fn as_mut(&mut self) -> &mut [::core::primitive::u8] {
+          &mut self.0
+        }
+      }
+
       impl ::core::convert::From<[::core::primitive::u8; $n]> for $name {
         #[inline]
         fn from(addr: [::core::primitive::u8; $n]) -> Self {
@@ -267,23 +578,71 @@ macro_rules! addr_ty {
         }
       }
 
+      impl ::core::convert::TryFrom<&[::core::primitive::u8]> for $name {
+        type Error = $crate::SliceLengthError;
+
+        #[inline]
+        fn try_from(src: &[::core::primitive::u8]) -> ::core::result::Result<Self, Self::Error> {
+          $name::ref_from_bytes(src).map(|addr| *addr)
+        }
+      }
+
       impl ::core::fmt::Debug for $name {
         #[inline]
         fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-          ::core::fmt::Display::fmt(self, f)
+          // Unlike `Display`, `{:#?}` keeps the colon-separated form.
+          let buf = self.to_colon_separated_array();
+          // SAFETY: The buffer is always valid UTF-8 as it only contains ASCII characters.
+          f.pad(unsafe { ::core::str::from_utf8_unchecked(&buf) })
         }
       }
 
+      /// Formats the address as colon-separated hex, or with `{:#}` as
+      /// hyphen-separated hex. Width, fill and alignment are honored.
       impl core::fmt::Display for $name {
         #[inline]
         fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-          let buf = self.to_colon_separated_array();
-          write!(
-            f,
-            "{}",
+          let buf = if f.alternate() {
+            self.to_hyphen_separated_array()
+          } else {
+            self.to_colon_separated_array()
+          };
+          // SAFETY: The buffer is always valid UTF-8 as it only contains ASCII characters.
+          f.pad(unsafe { ::core::str::from_utf8_unchecked(&buf) })
+        }
+      }
+
+      /// Formats the address as lowercase colon-separated hex, or with `{:#x}`
+      /// as a single `0x`-prefixed integer without separators.
+      impl ::core::fmt::LowerHex for $name {
+        #[inline]
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          if f.alternate() {
+            let buf = $crate::__private::prefixed_hex::<$n, { $n * 2 + 2 }>(&self.0, &$crate::__private::HEX_DIGITS);
             // SAFETY: The buffer is always valid UTF-8 as it only contains ASCII characters.
-            unsafe { ::core::str::from_utf8_unchecked(&buf) },
-          )
+            f.pad(unsafe { ::core::str::from_utf8_unchecked(&buf) })
+          } else {
+            let buf = self.to_colon_separated_array();
+            // SAFETY: The buffer is always valid UTF-8 as it only contains ASCII characters.
+            f.pad(unsafe { ::core::str::from_utf8_unchecked(&buf) })
+          }
+        }
+      }
+
+      /// Formats the address as uppercase colon-separated hex, or with `{:#X}`
+      /// as a single `0x`-prefixed integer without separators.
+      impl ::core::fmt::UpperHex for $name {
+        #[inline]
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          if f.alternate() {
+            let buf = $crate::__private::prefixed_hex::<$n, { $n * 2 + 2 }>(&self.0, &$crate::__private::UPPER_HEX_DIGITS);
+            // SAFETY: The buffer is always valid UTF-8 as it only contains ASCII characters.
+            f.pad(unsafe { ::core::str::from_utf8_unchecked(&buf) })
+          } else {
+            let buf = self.to_upper_colon_separated_array();
+            // SAFETY: The buffer is always valid UTF-8 as it only contains ASCII characters.
+            f.pad(unsafe { ::core::str::from_utf8_unchecked(&buf) })
+          }
         }
       }
     };
@@ -327,23 +686,122 @@ macro_rules! addr_ty {
     #[cfg(feature = "quickcheck")]
     $crate::__addr_ty_quickcheck! { $name[$n] }
 
+    #[cfg(feature = "rand")]
+    $crate::__addr_ty_rand! { $name[$n] }
+
     #[cfg(feature = "pyo3")]
     $crate::__addr_ty_pyo3! { $name[$n] }
 
     #[cfg(feature = "wasm-bindgen")]
     $crate::__addr_ty_wasm_bindgen! { $name[$n] }
+
+    #[cfg(feature = "bytemuck")]
+    $crate::__addr_ty_bytemuck! { $name[$n] }
   }
 }
 
+/// Adds the methods that read or set the IEEE 802 I/G and U/L bits of the
+/// first octet. IP over InfiniBand addresses start with reserved and queue
+/// pair flags instead, so `InfiniBandAddr` does not get them.
+macro_rules! ieee802_addr_ty {
+  ($name:ident) => {
+    impl $name {
+      /// Returns `true` if the I/G bit (least significant bit of the first
+      /// octet) is cleared, i.e. the address identifies a single station.
+      #[inline]
+      pub const fn is_unicast(&self) -> bool {
+        self.0[0] & $crate::__private::IG_BIT == 0
+      }
+
+      /// Returns `true` if the I/G bit (least significant bit of the first
+      /// octet) is set, i.e. the address identifies a group of stations.
+      #[inline]
+      pub const fn is_multicast(&self) -> bool {
+        !self.is_unicast()
+      }
+
+      /// Returns `true` if the U/L bit (second least significant bit of the
+      /// first octet) is cleared, i.e. the address is universally administered.
+      #[inline]
+      pub const fn is_universal(&self) -> bool {
+        self.0[0] & $crate::__private::UL_BIT == 0
+      }
+
+      /// Returns `true` if the U/L bit (second least significant bit of the
+      /// first octet) is set, i.e. the address is locally administered.
+      #[inline]
+      pub const fn is_local(&self) -> bool {
+        !self.is_universal()
+      }
+    }
+
+    #[cfg(feature = "rand")]
+    $crate::__addr_ty_rand_ieee802! { $name }
+  };
+}
+
 mod mac;
 pub use mac::*;
 
+mod any;
+pub use any::*;
+
+pub mod arp;
+
+pub mod dhcpv4;
+
+pub mod duid;
+
+mod batch;
+pub use batch::{format_many, parse_many, BatchParseError};
+
 mod eui64;
 pub use eui64::*;
 
+mod error;
+pub use error::*;
+
+pub mod ethernet;
+
 mod infini_band;
 pub use infini_band::*;
 
+pub mod ndp;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod neigh;
+
+pub mod netlink;
+
+mod format;
+pub use format::*;
+
+mod hex;
+
+pub mod lldp;
+
+mod fpe;
+pub use fpe::*;
+
+mod parse_options;
+pub use parse_options::*;
+
+mod pseudonym;
+pub use pseudonym::*;
+
+mod redact;
+pub use redact::*;
+
+mod scan;
+pub use scan::*;
+
+mod siphash;
+
+#[cfg(all(feature = "std", target_os = "linux"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "std", target_os = "linux"))))]
+pub mod sysfs;
+
 #[cfg(feature = "pyo3")]
 mod py;
 #[cfg(feature = "wasm-bindgen")]
@@ -355,11 +813,47 @@ mod arbitrary;
 #[cfg(feature = "quickcheck")]
 mod quickcheck;
 
+#[cfg(feature = "rand")]
+mod rand;
+
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
+
 #[doc(hidden)]
 pub mod __private {
   /// Lowercase ASCII hex digits for formatting.
   pub const HEX_DIGITS: [::core::primitive::u8; 16] = *b"0123456789abcdef";
 
+  /// Uppercase ASCII hex digits for formatting.
+  pub const UPPER_HEX_DIGITS: [::core::primitive::u8; 16] = *b"0123456789ABCDEF";
+
+  /// Writes `src` as a `0x`-prefixed run of hex digits. `L` must be `N * 2 + 2`.
+  #[inline]
+  pub const fn prefixed_hex<
+    const N: ::core::primitive::usize,
+    const L: ::core::primitive::usize,
+  >(
+    src: &[::core::primitive::u8; N],
+    digits: &[::core::primitive::u8; 16],
+  ) -> [::core::primitive::u8; L] {
+    let mut buf = [0u8; L];
+    buf[0] = b'0';
+    buf[1] = b'x';
+    let mut i = 0;
+    while i < N {
+      buf[2 + i * 2] = digits[(src[i] >> 4) as usize];
+      buf[3 + i * 2] = digits[(src[i] & 0xF) as usize];
+      i += 1;
+    }
+    buf
+  }
+
+  /// The I/G (individual/group) bit of the first octet.
+  pub const IG_BIT: ::core::primitive::u8 = 0x01;
+
+  /// The U/L (universal/local) bit of the first octet.
+  pub const UL_BIT: ::core::primitive::u8 = 0x02;
+
   /// Lookup table: ASCII byte → nibble value (`0..=15`), or `0xFF` for
   /// anything that isn't a valid hex digit. Branch-free alternative to
   /// chained `match` arms.
@@ -408,6 +902,9 @@ pub mod __private {
   #[cfg(feature = "quickcheck")]
   pub use quickcheck;
 
+  #[cfg(feature = "rand")]
+  pub use rand;
+
   #[cfg(feature = "pyo3")]
   pub use pyo3;
 
@@ -419,6 +916,12 @@ pub mod __private {
   #[cfg(feature = "wasm-bindgen")]
   pub use wasm_bindgen;
 
+  #[cfg(feature = "zerocopy")]
+  pub use zerocopy;
+
+  #[cfg(feature = "bytemuck")]
+  pub use bytemuck;
+
   #[cfg(any(feature = "alloc", feature = "std"))]
   pub use std::{
     boxed::Box,
@@ -427,6 +930,8 @@ pub mod __private {
   };
 
   pub use paste;
+
+  pub use crate::batch::{format_many_by, parse_many_by};
 }
 
 /// Converts a hexadecimal slice to an integer.
@@ -499,60 +1004,17 @@ const fn dot_separated_format_len<const N: ::core::primitive::usize>() -> ::core
   N * 2 + (N / 2 - 1)
 }
 
+#[inline]
+const fn bare_hex_format_len<const N: ::core::primitive::usize>() -> ::core::primitive::usize {
+  N * 2
+}
+
 #[inline]
 const fn colon_separated_format_len<const N: ::core::primitive::usize>() -> ::core::primitive::usize
 {
   N * 3 - 1
 }
 
-/// ParseError represents an error that occurred while parsing hex address.
-#[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
-pub enum ParseError<const N: ::core::primitive::usize> {
-  /// Returned when the input string has a invalid length.
-  #[error("invalid length: colon or hyphen separated format requires {ch_len} bytes, dot separated format requires {dlen} bytes, but got {0} bytes", ch_len = colon_separated_format_len::<N>(), dlen = dot_separated_format_len::<N>())]
-  InvalidLength(::core::primitive::usize),
-  /// Returned when the input string has an invalid seperator.
-  #[error("unexpected separator: expected {expected}, but got {actual}")]
-  UnexpectedSeparator {
-    /// The expected separator.
-    expected: u8,
-    /// The actual separator.
-    actual: u8,
-  },
-  /// Returned when the input string has an unexpected separator.
-  #[error("invalid separator: {0}")]
-  InvalidSeparator(u8),
-  /// Invalid digit.
-  #[error("invalid digit: {0:?}")]
-  InvalidHexDigit([::core::primitive::u8; 2]),
-}
-
-impl<const N: ::core::primitive::usize> ParseError<N> {
-  /// Returns the length of the address.
-  #[inline]
-  pub const fn invalid_length(len: ::core::primitive::usize) -> Self {
-    Self::InvalidLength(len)
-  }
-
-  /// Returns an error for an unexpected separator.
-  #[inline]
-  pub const fn unexpected_separator(expected: u8, actual: u8) -> Self {
-    Self::UnexpectedSeparator { expected, actual }
-  }
-
-  /// Returns an error for an invalid separator.
-  #[inline]
-  pub const fn invalid_separator(sep: u8) -> Self {
-    Self::InvalidSeparator(sep)
-  }
-
-  /// Returns an error for an invalid hex digit.
-  #[inline]
-  pub const fn invalid_hex_digit(digit: [::core::primitive::u8; 2]) -> Self {
-    Self::InvalidHexDigit(digit)
-  }
-}
-
 /// Parses s as an IEEE 802 MAC-48, EUI-48, EUI-64, or a 20-octet
 /// IP over InfiniBand link-layer address and etc using one of the following formats:
 ///
@@ -567,72 +1029,437 @@ impl<const N: ::core::primitive::usize> ParseError<N> {
 /// - Dot-separated:
 ///   - `0000.5e00.5301`
 ///   - `0200.5e10.0000.0001`
+///
+/// - Bare hex, optionally `0x`-prefixed:
+///   - `00005e005301`
+///   - `0x02005e1000000001`
 pub const fn parse<const N: ::core::primitive::usize>(
   src: &[u8],
 ) -> Result<[::core::primitive::u8; N], ParseError<N>> {
-  let dot_separated_len = dot_separated_format_len::<N>();
-  let colon_separated_len = colon_separated_format_len::<N>();
   let len = src.len();
 
-  if len == dot_separated_len {
-    let mut hw = [0u8; N];
-    let mut x = 0usize;
-    let mut i = 0usize;
+  if is_prefixed_bare_hex::<N>(src) {
+    return parse_bare_hex::<N>(src, 2);
+  }
 
-    while i < N {
-      // Validate the `.` separator between each 4-hex-digit group,
-      // except when we're at the end of the input.
-      if x + 4 < len && src[x + 4] != b'.' {
-        return Err(ParseError::unexpected_separator(b'.', src[x + 4]));
+  if len == bare_hex_format_len::<N>() {
+    return parse_bare_hex::<N>(src, 0);
+  }
+
+  if len == dot_separated_format_len::<N>() {
+    return parse_dot_separated::<N>(src);
+  }
+
+  if len == colon_separated_format_len::<N>() {
+    return parse_separated::<N>(src);
+  }
+
+  Err(ParseError::invalid_length(len))
+}
+
+/// Parses an address at the start of `src` and returns it together with the
+/// number of bytes it takes, so parsing can go on with the rest of `src`.
+///
+/// The notations are the ones [`parse`] accepts. The notation is picked from
+/// the first bytes (`0x`, a `:` or `-` after the first octet, or a `.` after
+/// the first group, bare hex otherwise) and exactly the length of that
+/// notation is consumed. The bytes after it are not looked at, so a caller
+/// expecting a delimiter has to check it.
+///
+/// ## Example
+///
+/// ```rust
+/// use hardware_address::parse_prefix;
+///
+/// let line = b"00:00:5e:00:53:01 dev eth0";
+/// let (addr, len) = parse_prefix::<6>(line).unwrap();
+/// assert_eq!(addr, [0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]);
+/// assert_eq!(&line[len..], b" dev eth0");
+/// ```
+pub const fn parse_prefix<const N: ::core::primitive::usize>(
+  src: &[u8],
+) -> Result<([::core::primitive::u8; N], ::core::primitive::usize), ParseError<N>> {
+  let len = src.len();
+
+  let (consumed, result) = if len >= 2 && src[0] == b'0' && (src[1] == b'x' || src[1] == b'X') {
+    let consumed = bare_hex_format_len::<N>() + 2;
+    if len < consumed {
+      return Err(ParseError::invalid_length(len));
+    }
+    (consumed, parse_bare_hex::<N>(src, 2))
+  } else if len > 2 && (src[2] == b':' || src[2] == b'-') {
+    let consumed = colon_separated_format_len::<N>();
+    if len < consumed {
+      return Err(ParseError::invalid_length(len));
+    }
+    (consumed, parse_separated::<N>(src))
+  } else if len > 4 && src[4] == b'.' {
+    let consumed = dot_separated_format_len::<N>();
+    if len < consumed {
+      return Err(ParseError::invalid_length(len));
+    }
+    (consumed, parse_dot_separated::<N>(src))
+  } else {
+    let consumed = bare_hex_format_len::<N>();
+    if len < consumed {
+      return Err(ParseError::invalid_length(len));
+    }
+    (consumed, parse_bare_hex::<N>(src, 0))
+  };
+
+  match result {
+    Ok(hw) => Ok((hw, consumed)),
+    Err(err) => Err(err),
+  }
+}
+
+/// Parses an address like [`parse`], but only accepts the notations and
+/// case allowed by `options`.
+///
+/// The notation is detected the same way [`parse`] does it, so an input
+/// that [`parse`] rejects gets the same error here. Otherwise a disallowed
+/// notation is reported as [`ParseErrorKind::DisallowedNotation`] before any
+/// digit is looked at, and a hex digit of the wrong case as
+/// [`ParseErrorKind::UnexpectedCase`].
+///
+/// ## Example
+///
+/// ```rust
+/// use hardware_address::{parse_with, Case, ParseErrorKind, ParseOptions};
+///
+/// let opts = ParseOptions::COLON.with_case(Some(Case::Lower));
+/// assert_eq!(
+///   parse_with::<6>(b"00:00:5e:00:53:01", opts),
+///   Ok([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]),
+/// );
+/// let err = parse_with::<6>(b"00:00:5E:00:53:01", opts).unwrap_err();
+/// assert_eq!(
+///   err.kind(),
+///   ParseErrorKind::UnexpectedCase { expected: Case::Lower, actual: b'E' },
+/// );
+/// assert_eq!(err.offset(), 7);
+/// ```
+pub const fn parse_with<const N: ::core::primitive::usize>(
+  src: &[u8],
+  options: ParseOptions,
+) -> Result<[::core::primitive::u8; N], ParseError<N>> {
+  let notation = match detect_notation::<N>(src) {
+    Some(notation) => notation,
+    None => return parse::<N>(src),
+  };
+  if !options.allows(notation) {
+    return Err(ParseError::disallowed_notation(notation));
+  }
+
+  let hw = match parse::<N>(src) {
+    Ok(hw) => hw,
+    Err(err) => return Err(err),
+  };
+
+  if let Some(case) = options.case() {
+    let mut i = if is_prefixed_bare_hex::<N>(src) { 2 } else { 0 };
+    while i < src.len() {
+      let b = src[i];
+      let wrong = match case {
+        Case::Lower => b.is_ascii_uppercase(),
+        Case::Upper => b.is_ascii_lowercase(),
+      };
+      if wrong {
+        return Err(ParseError::unexpected_case(case, b, i).with_notation(Some(notation)));
       }
+      i += 1;
+    }
+  }
+
+  Ok(hw)
+}
+
+/// Returns the notation [`parse`] would use for `src`, or `None` if it
+/// would reject `src` without looking at the digits.
+#[inline]
+const fn detect_notation<const N: ::core::primitive::usize>(src: &[u8]) -> Option<Notation> {
+  let len = src.len();
+  if is_prefixed_bare_hex::<N>(src) || len == bare_hex_format_len::<N>() {
+    Some(Notation::BareHex)
+  } else if len == dot_separated_format_len::<N>() {
+    Some(Notation::Dot)
+  } else if len == colon_separated_format_len::<N>() {
+    match src[2] {
+      b':' => Some(Notation::Colon),
+      b'-' => Some(Notation::Hyphen),
+      _ => None,
+    }
+  } else {
+    None
+  }
+}
+
+/// Parses an address like [`parse`], but tolerates loosely formatted input:
+///
+/// - leading and trailing ASCII whitespace is ignored (e.g. a trailing
+///   newline),
+/// - octets may have one or two hex digits, as printed by BSD
+///   `ether_ntoa` and some firmware (`0:1a:2b:3:4:5`),
+/// - octets may be separated by `:`, `-`, `.` or a space, and separators
+///   may be mixed (`00-1a:2b 3c.4d:5e`),
+/// - dot-separated groups may have one to four hex digits (`0:5e00:5301`
+///   is not accepted, but `0.5e00.5301` is),
+/// - hex digits may be of any case.
+///
+/// Everything [`parse`] accepts is accepted as well, with the same result.
+///
+/// ## Example
+///
+/// ```rust
+/// use hardware_address::parse_lenient;
+///
+/// assert_eq!(
+///   parse_lenient::<6>(b" 0:1a:2b:3:4:5\n"),
+///   Ok([0x00, 0x1a, 0x2b, 0x03, 0x04, 0x05]),
+/// );
+/// assert_eq!(
+///   parse_lenient::<6>(b"00 1A 2b-3c:4d:5e"),
+///   Ok([0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e]),
+/// );
+/// ```
+pub const fn parse_lenient<const N: ::core::primitive::usize>(
+  src: &[u8],
+) -> Result<[::core::primitive::u8; N], ParseError<N>> {
+  let mut start = 0;
+  let mut end = src.len();
+  while start < end && src[start].is_ascii_whitespace() {
+    start += 1;
+  }
+  while end > start && src[end - 1].is_ascii_whitespace() {
+    end -= 1;
+  }
+  let len = end - start;
 
-      match __private::hex_byte(src[x], src[x + 1]) {
-        Some(byte) => hw[i] = byte,
-        None => return Err(ParseError::invalid_hex_digit([src[x], src[x + 1]])),
+  if len == 0 {
+    return Err(ParseError::invalid_length(len));
+  }
+
+  // Bare hex: a single run of `2 * N` digits, optionally `0x`-prefixed.
+  let bare_len = bare_hex_format_len::<N>();
+  if len == bare_len + 2 && src[start] == b'0' && (src[start + 1] == b'x' || src[start + 1] == b'X')
+  {
+    return parse_bare_hex::<N>(src, start + 2);
+  }
+  if len == bare_len && !contains_lenient_separator(src, start, end) {
+    return parse_bare_hex::<N>(src, start);
+  }
+
+  // Split into groups of up to four hex digits.
+  let mut groups = [0u16; N];
+  let mut ngroups = 0usize;
+  let mut digits = 0usize;
+  let mut max_digits = 0usize;
+  let mut only_dots = true;
+  let mut i = start;
+
+  while i < end {
+    let b = src[i];
+    let val = __private::HEX_VAL[b as usize];
+    if val != 0xFF {
+      if digits == 4 || ngroups == N {
+        return Err(ParseError::invalid_length(len));
+      }
+      if digits == 0 {
+        groups[ngroups] = 0;
+      }
+      groups[ngroups] = (groups[ngroups] << 4) | val as u16;
+      digits += 1;
+    } else if is_lenient_separator(b) {
+      if digits == 0 {
+        // Leading or doubled separator: a digit was expected.
+        return Err(ParseError::invalid_hex_digit(b, i));
       }
-      match __private::hex_byte(src[x + 2], src[x + 3]) {
-        Some(byte) => hw[i + 1] = byte,
-        None => return Err(ParseError::invalid_hex_digit([src[x + 2], src[x + 3]])),
+      if digits > max_digits {
+        max_digits = digits;
       }
+      only_dots &= b == b'.';
+      ngroups += 1;
+      digits = 0;
+    } else if digits == 0 {
+      return Err(ParseError::invalid_hex_digit(b, i));
+    } else {
+      return Err(ParseError::invalid_separator(b, i));
+    }
+    i += 1;
+  }
+
+  if digits == 0 {
+    // Trailing separator.
+    return Err(ParseError::invalid_separator(src[end - 1], end - 1));
+  }
+  if digits > max_digits {
+    max_digits = digits;
+  }
+  ngroups += 1;
 
-      x += 5;
-      i += 2;
+  let mut hw = [0u8; N];
+  if ngroups == N && max_digits <= 2 {
+    let mut i = 0;
+    while i < N {
+      hw[i] = groups[i] as u8;
+      i += 1;
     }
+    return Ok(hw);
+  }
 
+  if N % 2 == 0 && ngroups == N / 2 && only_dots {
+    let mut i = 0;
+    while i < N / 2 {
+      hw[i * 2] = (groups[i] >> 8) as u8;
+      hw[i * 2 + 1] = groups[i] as u8;
+      i += 1;
+    }
     return Ok(hw);
   }
 
-  if len == colon_separated_len {
-    let sep = src[2];
-    if sep != b':' && sep != b'-' {
-      return Err(ParseError::invalid_separator(sep));
+  Err(ParseError::invalid_length(len))
+}
+
+#[inline]
+const fn is_lenient_separator(b: u8) -> bool {
+  matches!(b, b':' | b'-' | b'.' | b' ')
+}
+
+#[inline]
+const fn contains_lenient_separator(
+  src: &[u8],
+  start: ::core::primitive::usize,
+  end: ::core::primitive::usize,
+) -> bool {
+  let mut i = start;
+  while i < end {
+    if is_lenient_separator(src[i]) {
+      return true;
     }
+    i += 1;
+  }
+  false
+}
 
-    let mut hw = [0u8; N];
-    let mut x = 0usize;
-    let mut i = 0usize;
+/// Returns `true` if `src` has the length of a `0x`-prefixed bare hex
+/// address and starts with `0x` or `0X`.
+#[inline]
+const fn is_prefixed_bare_hex<const N: ::core::primitive::usize>(src: &[u8]) -> bool {
+  src.len() == bare_hex_format_len::<N>() + 2
+    && src[0] == b'0'
+    && (src[1] == b'x' || src[1] == b'X')
+}
 
-    while i < N {
-      if x + 2 < len {
-        let csep = src[x + 2];
-        if csep != sep {
-          return Err(ParseError::unexpected_separator(sep, csep));
-        }
-      }
+/// Parses `2 * N` hex digits starting at `offset`, `src` must be at least
+/// `offset + 2 * N` bytes long.
+const fn parse_bare_hex<const N: ::core::primitive::usize>(
+  src: &[u8],
+  offset: ::core::primitive::usize,
+) -> Result<[::core::primitive::u8; N], ParseError<N>> {
+  let mut hw = [0u8; N];
+  let mut i = 0usize;
+
+  while i < N {
+    let x = offset + i * 2;
+    match __private::hex_byte(src[x], src[x + 1]) {
+      Some(byte) => hw[i] = byte,
+      None => return Err(invalid_hex_pair(src, x, Notation::BareHex)),
+    }
+    i += 1;
+  }
+
+  Ok(hw)
+}
+
+/// Parses the dot-separated form at the start of `src`, which must be at
+/// least as long as that form. Trailing bytes are not looked at.
+const fn parse_dot_separated<const N: ::core::primitive::usize>(
+  src: &[u8],
+) -> Result<[::core::primitive::u8; N], ParseError<N>> {
+  let len = dot_separated_format_len::<N>();
+  let mut hw = [0u8; N];
+  let mut x = 0usize;
+  let mut i = 0usize;
+
+  while i < N {
+    // Validate the `.` separator between each 4-hex-digit group,
+    // except when we're at the end of the input.
+    if x + 4 < len && src[x + 4] != b'.' {
+      return Err(
+        ParseError::unexpected_separator(b'.', src[x + 4], x + 4)
+          .with_notation(Some(Notation::Dot)),
+      );
+    }
+
+    match __private::hex_byte(src[x], src[x + 1]) {
+      Some(byte) => hw[i] = byte,
+      None => return Err(invalid_hex_pair(src, x, Notation::Dot)),
+    }
+    match __private::hex_byte(src[x + 2], src[x + 3]) {
+      Some(byte) => hw[i + 1] = byte,
+      None => return Err(invalid_hex_pair(src, x + 2, Notation::Dot)),
+    }
+
+    x += 5;
+    i += 2;
+  }
+
+  Ok(hw)
+}
 
-      match __private::hex_byte(src[x], src[x + 1]) {
-        Some(byte) => hw[i] = byte,
-        None => return Err(ParseError::invalid_hex_digit([src[x], src[x + 1]])),
+/// Parses the colon- or hyphen-separated form at the start of `src`, which
+/// must be at least as long as that form. Trailing bytes are not looked at.
+const fn parse_separated<const N: ::core::primitive::usize>(
+  src: &[u8],
+) -> Result<[::core::primitive::u8; N], ParseError<N>> {
+  let len = colon_separated_format_len::<N>();
+  let sep = src[2];
+  let notation = match sep {
+    b':' => Notation::Colon,
+    b'-' => Notation::Hyphen,
+    _ => return Err(ParseError::invalid_separator(sep, 2)),
+  };
+
+  let mut hw = [0u8; N];
+  let mut x = 0usize;
+  let mut i = 0usize;
+
+  while i < N {
+    if x + 2 < len {
+      let csep = src[x + 2];
+      if csep != sep {
+        return Err(
+          ParseError::unexpected_separator(sep, csep, x + 2).with_notation(Some(notation)),
+        );
       }
+    }
 
-      x += 3;
-      i += 1;
+    match __private::hex_byte(src[x], src[x + 1]) {
+      Some(byte) => hw[i] = byte,
+      None => return Err(invalid_hex_pair(src, x, notation)),
     }
 
-    return Ok(hw);
+    x += 3;
+    i += 1;
   }
 
-  Err(ParseError::invalid_length(len))
+  Ok(hw)
+}
+
+/// Returns the error for the hex digit pair at `x`, of which at least one
+/// digit is invalid.
+#[inline]
+const fn invalid_hex_pair<const N: ::core::primitive::usize>(
+  src: &[u8],
+  x: ::core::primitive::usize,
+  notation: Notation,
+) -> ParseError<N> {
+  let x = if __private::HEX_VAL[src[x] as usize] == 0xFF {
+    x
+  } else {
+    x + 1
+  };
+  ParseError::invalid_hex_digit(src[x], x).with_notation(Some(notation))
 }
 
 #[cfg(test)]
@@ -710,6 +1537,186 @@ mod tests {
     assert_eq!(MAC3, [0x00, 0x00, 0x5E, 0x00, 0x53, 0x01]);
   }
 
+  #[test]
+  fn test_parse_with() {
+    const MAC: [u8; 6] = [0x00, 0x00, 0x5e, 0x00, 0x53, 0x01];
+    let inputs: [(&[u8], Notation); 5] = [
+      (b"00:00:5e:00:53:01", Notation::Colon),
+      (b"00-00-5e-00-53-01", Notation::Hyphen),
+      (b"0000.5e00.5301", Notation::Dot),
+      (b"00005e005301", Notation::BareHex),
+      (b"0x00005e005301", Notation::BareHex),
+    ];
+    for (src, notation) in inputs {
+      assert_eq!(parse_with::<6>(src, ParseOptions::new()), Ok(MAC));
+      let only = ParseOptions::new()
+        .with_notation(Notation::Colon, false)
+        .with_notation(Notation::Hyphen, false)
+        .with_notation(Notation::Dot, false)
+        .with_bare_hex(false)
+        .with_notation(notation, true);
+      assert_eq!(parse_with::<6>(src, only), Ok(MAC));
+      assert_eq!(
+        parse_with::<6>(src, ParseOptions::new().with_notation(notation, false)),
+        Err(ParseError::disallowed_notation(notation))
+      );
+    }
+
+    // Case is checked on hex digits only, not on the `0x` prefix.
+    let lower = ParseOptions::new().with_case(Some(Case::Lower));
+    let upper = ParseOptions::new().with_case(Some(Case::Upper));
+    assert_eq!(parse_with::<6>(b"0X00005e005301", lower), Ok(MAC));
+    assert_eq!(parse_with::<6>(b"0x00005E005301", upper), Ok(MAC));
+    assert_eq!(
+      parse_with::<6>(b"0x00005e005301", upper),
+      Err(ParseError::unexpected_case(Case::Upper, b'e', 7).with_notation(Some(Notation::BareHex)))
+    );
+    assert_eq!(
+      parse_with::<6>(b"AA:BB:cc:DD:EE:FF", upper),
+      Err(ParseError::unexpected_case(Case::Upper, b'c', 6).with_notation(Some(Notation::Colon)))
+    );
+    assert_eq!(
+      parse_with::<6>(b"0:0:5e:0:53:1", lower),
+      parse::<6>(b"0:0:5e:0:53:1")
+    );
+    assert_eq!(
+      parse_with::<6>(b"00.00.5e.00.53.01", ParseOptions::COLON),
+      Err(ParseError::invalid_separator(b'.', 2))
+    );
+    // Disallowed notations are reported before invalid digits.
+    assert_eq!(
+      parse_with::<6>(b"00-00-5g-00-53-01", ParseOptions::COLON),
+      Err(ParseError::disallowed_notation(Notation::Hyphen))
+    );
+
+    const CONST: [u8; 6] = match parse_with::<6>(b"00:00:5e:00:53:01", ParseOptions::COLON) {
+      Ok(v) => v,
+      Err(_) => panic!(),
+    };
+    assert_eq!(CONST, MAC);
+  }
+
+  #[test]
+  fn test_parse_prefix() {
+    const MAC: [u8; 6] = [0x00, 0x00, 0x5e, 0x00, 0x53, 0x01];
+    let ok: [(&[u8], usize); 7] = [
+      (b"00:00:5e:00:53:01 dev eth0", 17),
+      (b"00-00-5e-00-53-01", 17),
+      (b"00:00:5e:00:53:01:02", 17),
+      (b"0000.5e00.5301,", 14),
+      (b"00005e005301\n", 12),
+      (b"0x00005e005301ff", 14),
+      (b"0X00005E005301", 14),
+    ];
+    for (src, len) in ok {
+      assert_eq!(parse_prefix::<6>(src), Ok((MAC, len)));
+    }
+
+    assert_eq!(
+      parse_prefix::<6>(b"00:00:5e:00:53"),
+      Err(ParseError::invalid_length(14))
+    );
+    assert_eq!(parse_prefix::<6>(b""), Err(ParseError::invalid_length(0)));
+    assert_eq!(parse_prefix::<6>(b"0x"), Err(ParseError::invalid_length(2)));
+    assert_eq!(
+      parse_prefix::<6>(b"00:00-5e:00:53:01"),
+      Err(ParseError::unexpected_separator(b':', b'-', 5).with_notation(Some(Notation::Colon)))
+    );
+    assert_eq!(
+      parse_prefix::<6>(b"0000.5e00-5301"),
+      Err(ParseError::unexpected_separator(b'.', b'-', 9).with_notation(Some(Notation::Dot)))
+    );
+    assert_eq!(
+      parse_prefix::<6>(b"00 00 5e 00 53 01"),
+      Err(ParseError::invalid_hex_digit(b' ', 2).with_notation(Some(Notation::BareHex)))
+    );
+
+    const CONST: ([u8; 6], usize) = match parse_prefix::<6>(b"00:00:5e:00:53:01 up") {
+      Ok(v) => v,
+      Err(_) => panic!(),
+    };
+    assert_eq!(CONST, (MAC, 17));
+  }
+
+  #[test]
+  fn test_parse_lenient() {
+    const MAC: [u8; 6] = [0x00, 0x1a, 0x2b, 0x03, 0x04, 0x05];
+    let ok: &[&[u8]] = &[
+      b"0:1a:2b:3:4:5",
+      b"00:1a:2b:03:04:05",
+      b"0-1A-2b-3-4-5",
+      b"0 1a 2b 3 4 5",
+      b"  0:1a-2B 3.4:5\r\n",
+      b"001a.2b03.0405",
+      b"1a.2b03.405",
+      b"001a2b030405",
+      b"0x001a2b030405",
+      b"\t0X001A2B030405 ",
+    ];
+    for src in ok {
+      let expected = if src.starts_with(b"1a") {
+        [0x00, 0x1a, 0x2b, 0x03, 0x04, 0x05]
+      } else {
+        MAC
+      };
+      assert_eq!(
+        parse_lenient::<6>(src),
+        Ok(expected),
+        "{:?}",
+        core::str::from_utf8(src)
+      );
+    }
+
+    const EUI: [u8; 8] = [0x02, 0x00, 0x5e, 0x10, 0x00, 0x00, 0x00, 0x01];
+    assert_eq!(parse_lenient::<8>(b"2:0:5e:10:0:0:0:1"), Ok(EUI));
+    assert_eq!(parse_lenient::<8>(b"200.5e10.0.1"), Ok(EUI));
+
+    // Errors.
+    assert_eq!(
+      parse_lenient::<6>(b" \n"),
+      Err(ParseError::invalid_length(0))
+    );
+    assert_eq!(
+      parse_lenient::<6>(b"0:1a:2b:3:4"),
+      Err(ParseError::invalid_length(11))
+    );
+    assert_eq!(
+      parse_lenient::<6>(b"0:1a:2b:3:4:5:6"),
+      Err(ParseError::invalid_length(15))
+    );
+    assert_eq!(
+      parse_lenient::<6>(b"0:1a2:2b:3:4:5"),
+      Err(ParseError::invalid_length(14))
+    );
+    assert_eq!(
+      parse_lenient::<6>(b"0:1a::3:4:5"),
+      Err(ParseError::invalid_hex_digit(b':', 5))
+    );
+    assert_eq!(
+      parse_lenient::<6>(b"0:1a:2b:3:4:"),
+      Err(ParseError::invalid_separator(b':', 11))
+    );
+    assert_eq!(
+      parse_lenient::<6>(b"0:1a:2b:3:4;5"),
+      Err(ParseError::invalid_separator(b';', 11))
+    );
+    assert_eq!(
+      parse_lenient::<6>(b"0:1a:2b:3:4:g"),
+      Err(ParseError::invalid_hex_digit(b'g', 12))
+    );
+    // Dot groups must all be dots.
+    assert_eq!(
+      parse_lenient::<6>(b"001a:2b03.0405"),
+      Err(ParseError::invalid_length(14))
+    );
+
+    const CONST: [u8; 6] = match parse_lenient::<6>(b"0:1a:2b:3:4:5") {
+      Ok(v) => v,
+      Err(_) => panic!(),
+    };
+    assert_eq!(CONST, MAC);
+  }
+
   /// Fast-path `hex_byte` sanity: all valid digits, plus a few
   /// invalids at boundary values.
   #[test]
@@ -0,0 +1,527 @@
+//! Link Layer Discovery Protocol (IEEE 802.1AB) Chassis ID and Port ID
+//! TLVs, and the destination addresses of LLDPDUs.
+//!
+//! [`ChassisId`] and [`PortId`] borrow their identifiers from the buffer
+//! they were decoded from; the MAC address subtype is decoded into a
+//! [`MacAddr`].
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hardware_address::{lldp::{ChassisId, LldpScope, PortId}, MacAddr};
+//!
+//! let mac = MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]);
+//! let mut buf = [0u8; 64];
+//! let mut len = ChassisId::MacAddr(mac).encode(&mut buf).unwrap();
+//! len += PortId::InterfaceName(b"eth0").encode(&mut buf[len..]).unwrap();
+//!
+//! let chassis = ChassisId::decode(&buf).unwrap();
+//! assert_eq!(chassis, ChassisId::MacAddr(mac));
+//! let port = PortId::decode(&buf[chassis.encoded_len()..len]).unwrap();
+//! assert_eq!(port, PortId::InterfaceName(b"eth0"));
+//!
+//! assert_eq!(LldpScope::NearestBridge.destination().to_string(), "01:80:c2:00:00:0e");
+//! ```
+
+use crate::{
+  ethernet::{EtherType, EthernetHeaderBuilder, TypeOrLength},
+  MacAddr,
+};
+
+/// An error returned while decoding or encoding an LLDP TLV.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+pub enum LldpError {
+  /// The buffer is too short for the TLV.
+  #[error("buffer too short: expected at least {expected} bytes, but got {actual} bytes")]
+  TooShort {
+    /// The number of bytes the TLV needs.
+    expected: usize,
+    /// The length of the buffer.
+    actual: usize,
+  },
+  /// The TLV is of another type.
+  #[error("unexpected TLV type: expected {expected}, but got {actual}")]
+  UnexpectedType {
+    /// The expected TLV type.
+    expected: u8,
+    /// The actual TLV type.
+    actual: u8,
+  },
+  /// The identifier is empty, longer than 255 bytes, or of the wrong length
+  /// for its subtype, e.g. a MAC address that is not 6 bytes.
+  #[error("invalid identifier length of {0} bytes")]
+  InvalidLength(usize),
+  /// An `Other` identifier uses a subtype that has its own variant, so it
+  /// would not decode back to `Other`.
+  #[error("subtype {0} is not reserved")]
+  UnreservedSubtype(u8),
+}
+
+/// The length of a TLV header.
+const HEADER_LEN: usize = 2;
+
+/// The longest identifier of a Chassis ID or Port ID TLV.
+const MAX_ID_LEN: usize = 255;
+
+/// Splits the Chassis ID or Port ID TLV of type `ty` at the start of `src`
+/// into its subtype and identifier.
+fn decode_tlv(src: &[u8], ty: u8) -> Result<(u8, &[u8]), LldpError> {
+  if src.len() < HEADER_LEN {
+    return Err(LldpError::TooShort {
+      expected: HEADER_LEN,
+      actual: src.len(),
+    });
+  }
+  let header = u16::from_be_bytes([src[0], src[1]]);
+  let actual = (header >> 9) as u8;
+  if actual != ty {
+    return Err(LldpError::UnexpectedType {
+      expected: ty,
+      actual,
+    });
+  }
+  let len = (header & 0x01FF) as usize;
+  // The subtype and at least one byte of identifier.
+  if !(2..=MAX_ID_LEN + 1).contains(&len) {
+    return Err(LldpError::InvalidLength(len.saturating_sub(1)));
+  }
+  let expected = HEADER_LEN + len;
+  if src.len() < expected {
+    return Err(LldpError::TooShort {
+      expected,
+      actual: src.len(),
+    });
+  }
+  Ok((src[HEADER_LEN], &src[HEADER_LEN + 1..expected]))
+}
+
+/// Writes a Chassis ID or Port ID TLV whose identifier is the concatenation
+/// of `parts`.
+fn encode_tlv(buf: &mut [u8], ty: u8, subtype: u8, parts: [&[u8]; 2]) -> Result<usize, LldpError> {
+  let id_len = parts[0].len() + parts[1].len();
+  if id_len == 0 || id_len > MAX_ID_LEN {
+    return Err(LldpError::InvalidLength(id_len));
+  }
+  let len = HEADER_LEN + 1 + id_len;
+  if buf.len() < len {
+    return Err(LldpError::TooShort {
+      expected: len,
+      actual: buf.len(),
+    });
+  }
+  let header = (ty as u16) << 9 | (id_len as u16 + 1);
+  buf[..2].copy_from_slice(&header.to_be_bytes());
+  buf[2] = subtype;
+  let (first, second) = buf[3..len].split_at_mut(parts[0].len());
+  first.copy_from_slice(parts[0]);
+  second.copy_from_slice(parts[1]);
+  Ok(len)
+}
+
+/// A network address identifier: an IANA address family number followed by
+/// the address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NetworkAddr<'a> {
+  family: u8,
+  addr: &'a [u8],
+}
+
+impl<'a> NetworkAddr<'a> {
+  /// The IANA address family number of IPv4.
+  pub const IPV4: u8 = 1;
+
+  /// The IANA address family number of IPv6.
+  pub const IPV6: u8 = 2;
+
+  /// Creates a network address of address family `family`.
+  #[inline]
+  pub const fn new(family: u8, addr: &'a [u8]) -> Self {
+    Self { family, addr }
+  }
+
+  /// Returns the IANA address family number.
+  #[inline]
+  pub const fn family(&self) -> u8 {
+    self.family
+  }
+
+  /// Returns the address bytes.
+  #[inline]
+  pub const fn addr(&self) -> &'a [u8] {
+    self.addr
+  }
+
+  /// Returns the address if it is an IPv4 address.
+  #[inline]
+  pub fn ipv4(&self) -> Option<[u8; 4]> {
+    match self.family {
+      Self::IPV4 => self.addr.try_into().ok(),
+      _ => None,
+    }
+  }
+
+  /// Returns the address if it is an IPv6 address.
+  #[inline]
+  pub fn ipv6(&self) -> Option<[u8; 16]> {
+    match self.family {
+      Self::IPV6 => self.addr.try_into().ok(),
+      _ => None,
+    }
+  }
+}
+
+macro_rules! id_tlv {
+  (
+    $(#[$attr:meta])*
+    $name:ident = $ty:literal {
+      mac = $mac:literal,
+      network = $network:literal,
+      $($(#[$vattr:meta])* $variant:ident = $subtype:literal,)+
+    }
+  ) => {
+    $(#[$attr])*
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum $name<'a> {
+      $(
+        $(#[$vattr])*
+        $variant(&'a [u8]),
+      )+
+      #[doc = concat!("A MAC address, subtype ", $mac, ".")]
+      MacAddr(MacAddr),
+      #[doc = concat!("A network address, subtype ", $network, ".")]
+      NetworkAddr(NetworkAddr<'a>),
+      /// A reserved subtype.
+      Other {
+        /// The subtype.
+        subtype: u8,
+        /// The identifier.
+        id: &'a [u8],
+      },
+    }
+
+    impl<'a> $name<'a> {
+      /// The TLV type.
+      pub const TLV_TYPE: u8 = $ty;
+
+      /// Decodes the TLV at the start of `src`.
+      pub fn decode(src: &'a [u8]) -> Result<Self, LldpError> {
+        let (subtype, id) = decode_tlv(src, $ty)?;
+        Ok(match subtype {
+          $($subtype => Self::$variant(id),)+
+          $mac => match MacAddr::try_from(id) {
+            Ok(addr) => Self::MacAddr(addr),
+            Err(_) => return Err(LldpError::InvalidLength(id.len())),
+          },
+          $network => match id.split_first() {
+            Some((&family, addr)) if !addr.is_empty() => {
+              Self::NetworkAddr(NetworkAddr::new(family, addr))
+            }
+            _ => return Err(LldpError::InvalidLength(id.len())),
+          },
+          subtype => Self::Other { subtype, id },
+        })
+      }
+
+      /// Returns the subtype.
+      #[inline]
+      pub const fn subtype(&self) -> u8 {
+        match self {
+          $(Self::$variant(_) => $subtype,)+
+          Self::MacAddr(_) => $mac,
+          Self::NetworkAddr(_) => $network,
+          Self::Other { subtype, .. } => *subtype,
+        }
+      }
+
+      #[inline]
+      fn parts(&self) -> [&[u8]; 2] {
+        match self {
+          $(Self::$variant(id) => [*id, &[]],)+
+          Self::MacAddr(addr) => [addr.as_bytes(), &[]],
+          Self::NetworkAddr(addr) => [::core::slice::from_ref(&addr.family), addr.addr],
+          Self::Other { id, .. } => [*id, &[]],
+        }
+      }
+
+      /// Returns the length of the encoded TLV, including its header.
+      #[inline]
+      pub fn encoded_len(&self) -> usize {
+        let [a, b] = self.parts();
+        HEADER_LEN + 1 + a.len() + b.len()
+      }
+
+      /// Encodes the TLV to the start of `buf`, returning its length.
+      ///
+      /// Fails if the identifier is empty or longer than 255 bytes, if a
+      /// network address has no address bytes, or if an `Other` identifier
+      /// uses a subtype that is not reserved, so that every encoded TLV
+      /// decodes back to `self`.
+      pub fn encode(&self, buf: &mut [u8]) -> Result<usize, LldpError> {
+        match *self {
+          Self::NetworkAddr(addr) if addr.addr.is_empty() => {
+            return Err(LldpError::InvalidLength(1));
+          }
+          Self::Other { subtype, .. }
+            if subtype == $mac || subtype == $network $(|| subtype == $subtype)+ =>
+          {
+            return Err(LldpError::UnreservedSubtype(subtype));
+          }
+          _ => {}
+        }
+        encode_tlv(buf, $ty, self.subtype(), self.parts())
+      }
+    }
+  };
+}
+
+id_tlv! {
+  /// A Chassis ID TLV, identifying the chassis of the sending station.
+  ChassisId = 1 {
+    mac = 4,
+    network = 5,
+    /// The `entPhysicalAlias` of a chassis component, subtype 1.
+    ChassisComponent = 1,
+    /// The `ifAlias` of an interface, subtype 2.
+    InterfaceAlias = 2,
+    /// The `entPhysicalAlias` of a port or backplane component, subtype 3.
+    PortComponent = 3,
+    /// The `ifName` of an interface, subtype 6.
+    InterfaceName = 6,
+    /// A locally assigned identifier, subtype 7.
+    Local = 7,
+  }
+}
+
+id_tlv! {
+  /// A Port ID TLV, identifying the sending port.
+  PortId = 2 {
+    mac = 3,
+    network = 4,
+    /// The `ifAlias` of the interface, subtype 1.
+    InterfaceAlias = 1,
+    /// The `entPhysicalAlias` of the port component, subtype 2.
+    PortComponent = 2,
+    /// The `ifName` of the interface, subtype 5.
+    InterfaceName = 5,
+    /// An agent circuit ID ([RFC 3046]), subtype 6.
+    ///
+    /// [RFC 3046]: https://www.rfc-editor.org/rfc/rfc3046
+    AgentCircuitId = 6,
+    /// A locally assigned identifier, subtype 7.
+    Local = 7,
+  }
+}
+
+/// How far an LLDPDU propagates, which selects its destination address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LldpScope {
+  /// Only to the nearest bridge, `01:80:c2:00:00:0e`. The default for most
+  /// agents.
+  NearestBridge,
+  /// Through two-port MAC relays to the nearest non-TPMR bridge,
+  /// `01:80:c2:00:00:03`.
+  NearestNonTpmrBridge,
+  /// Through provider bridges to the nearest customer bridge,
+  /// `01:80:c2:00:00:00`.
+  NearestCustomerBridge,
+}
+
+impl LldpScope {
+  /// Returns the destination address of LLDPDUs of this scope.
+  #[inline]
+  pub const fn destination(self) -> MacAddr {
+    let last = match self {
+      Self::NearestBridge => 0x0e,
+      Self::NearestNonTpmrBridge => 0x03,
+      Self::NearestCustomerBridge => 0x00,
+    };
+    MacAddr::from_raw([0x01, 0x80, 0xc2, 0x00, 0x00, last])
+  }
+
+  /// Returns the scope whose destination address is `addr`, if any.
+  #[inline]
+  pub fn from_destination(addr: &MacAddr) -> Option<Self> {
+    [
+      Self::NearestBridge,
+      Self::NearestNonTpmrBridge,
+      Self::NearestCustomerBridge,
+    ]
+    .into_iter()
+    .find(|scope| scope.destination() == *addr)
+  }
+
+  /// Returns the Ethernet header of an LLDPDU of this scope sent from
+  /// `source`.
+  #[inline]
+  pub const fn ethernet_header(self, source: MacAddr) -> EthernetHeaderBuilder {
+    EthernetHeaderBuilder::new(
+      self.destination(),
+      source,
+      TypeOrLength::EtherType(EtherType::Lldp),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const MAC: MacAddr = MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]);
+
+  #[test]
+  fn chassis_id() {
+    let mut buf = [0u8; 16];
+    assert_eq!(ChassisId::MacAddr(MAC).encode(&mut buf), Ok(9));
+    assert_eq!(
+      buf[..9],
+      [0x02, 0x07, 0x04, 0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]
+    );
+    assert_eq!(ChassisId::decode(&buf[..9]), Ok(ChassisId::MacAddr(MAC)));
+
+    let ids = [
+      ChassisId::ChassisComponent(b"chassis"),
+      ChassisId::InterfaceAlias(b"uplink"),
+      ChassisId::PortComponent(b"slot1"),
+      ChassisId::NetworkAddr(NetworkAddr::new(NetworkAddr::IPV4, &[192, 0, 2, 1])),
+      ChassisId::InterfaceName(b"eth0"),
+      ChassisId::Local(b"sw1"),
+      ChassisId::Other {
+        subtype: 0,
+        id: b"x",
+      },
+    ];
+    for id in ids {
+      let len = id.encode(&mut buf).unwrap();
+      assert_eq!(len, id.encoded_len());
+      assert_eq!(ChassisId::decode(&buf[..len]), Ok(id));
+    }
+  }
+
+  #[test]
+  fn port_id() {
+    // A Port ID TLV of an IPv6 network address.
+    let mut tlv = [0u8; 20];
+    tlv[..8].copy_from_slice(&[0x04, 0x12, 0x04, 0x02, 0x20, 0x01, 0x0d, 0xb8]);
+    tlv[19] = 0x01;
+    let id = PortId::decode(&tlv).unwrap();
+    let addr = match id {
+      PortId::NetworkAddr(addr) => addr,
+      id => panic!("{id:?}"),
+    };
+    assert_eq!(addr.family(), NetworkAddr::IPV6);
+    assert_eq!(addr.ipv6().unwrap()[..4], [0x20, 0x01, 0x0d, 0xb8]);
+    assert_eq!(addr.ipv4(), None);
+    assert_eq!(id.subtype(), 4);
+
+    let mut buf = [0u8; 32];
+    assert_eq!(id.encode(&mut buf), Ok(tlv.len()));
+    assert_eq!(buf[..tlv.len()], tlv);
+
+    let len = PortId::MacAddr(MAC).encode(&mut buf).unwrap();
+    assert_eq!(buf[2], 3);
+    assert_eq!(PortId::decode(&buf[..len]), Ok(PortId::MacAddr(MAC)));
+    let len = PortId::AgentCircuitId(b"\x01\x02")
+      .encode(&mut buf)
+      .unwrap();
+    assert_eq!(PortId::decode(&buf[..len]).unwrap().subtype(), 6);
+  }
+
+  #[test]
+  fn errors() {
+    assert_eq!(
+      PortId::decode(&[0x02]),
+      Err(LldpError::TooShort {
+        expected: 2,
+        actual: 1
+      })
+    );
+    assert_eq!(
+      PortId::decode(&[0x02, 0x07, 0x03]),
+      Err(LldpError::UnexpectedType {
+        expected: 2,
+        actual: 1
+      })
+    );
+    assert_eq!(
+      ChassisId::decode(&[0x02, 0x07, 0x04, 0x00]),
+      Err(LldpError::TooShort {
+        expected: 9,
+        actual: 4
+      })
+    );
+    // A MAC address of 5 bytes.
+    assert_eq!(
+      ChassisId::decode(&[0x02, 0x06, 0x04, 0, 0, 0, 0, 0]),
+      Err(LldpError::InvalidLength(5))
+    );
+    // Only a subtype.
+    assert_eq!(
+      ChassisId::decode(&[0x02, 0x01, 0x07]),
+      Err(LldpError::InvalidLength(0))
+    );
+    // A network address without an address.
+    assert_eq!(
+      PortId::decode(&[0x04, 0x02, 0x04, 0x01]),
+      Err(LldpError::InvalidLength(1))
+    );
+
+    let mut buf = [0u8; 300];
+    assert_eq!(
+      PortId::Local(b"").encode(&mut buf),
+      Err(LldpError::InvalidLength(0))
+    );
+    assert_eq!(
+      PortId::Local(&[b'a'; 256]).encode(&mut buf),
+      Err(LldpError::InvalidLength(256))
+    );
+    assert_eq!(
+      PortId::NetworkAddr(NetworkAddr::new(NetworkAddr::IPV4, &[])).encode(&mut buf),
+      Err(LldpError::InvalidLength(1))
+    );
+    assert_eq!(
+      ChassisId::Other {
+        subtype: 4,
+        id: MAC.as_bytes(),
+      }
+      .encode(&mut buf),
+      Err(LldpError::UnreservedSubtype(4))
+    );
+    assert_eq!(
+      PortId::Other {
+        subtype: 6,
+        id: b"circuit",
+      }
+      .encode(&mut buf),
+      Err(LldpError::UnreservedSubtype(6))
+    );
+    assert_eq!(
+      PortId::Local(b"port").encode(&mut buf[..6]),
+      Err(LldpError::TooShort {
+        expected: 7,
+        actual: 6
+      })
+    );
+  }
+
+  #[test]
+  fn scopes() {
+    for scope in [
+      LldpScope::NearestBridge,
+      LldpScope::NearestNonTpmrBridge,
+      LldpScope::NearestCustomerBridge,
+    ] {
+      let dst = scope.destination();
+      assert!(dst.is_multicast() && dst.is_universal());
+      assert_eq!(LldpScope::from_destination(&dst), Some(scope));
+    }
+    assert_eq!(LldpScope::from_destination(&MAC), None);
+
+    let header = LldpScope::NearestNonTpmrBridge.ethernet_header(MAC);
+    let mut buf = [0u8; 14];
+    header.write(&mut buf).unwrap();
+    assert_eq!(
+      buf,
+      [0x01, 0x80, 0xc2, 0x00, 0x00, 0x03, 0x00, 0x00, 0x5e, 0x00, 0x53, 0x01, 0x88, 0xcc]
+    );
+  }
+}
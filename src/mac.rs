@@ -1,15 +1,99 @@
+use crate::{siphash::SipHasher24, AddrCipher, Pseudonymizer};
+
 addr_ty!(
   /// Represents a physical hardware address (MAC address).
+  ///
+  /// `Display` prints the colon-separated form (`00:00:5e:00:53:01`), and
+  /// `{:#}` the IEEE 802 hyphen-separated form (`00-00-5e-00-53-01`). Width,
+  /// fill and alignment are honored, e.g. `{:>20}`.
   #[doc(alias = "Eui48Addr")]
   MacAddr[6]
 );
 
+ieee802_addr_ty!(MacAddr);
+
+impl MacAddr {
+  /// Derives a stable, locally administered unicast address from a secret
+  /// `key` and a list of seed `parts` (e.g. a machine id and an interface
+  /// name), in the spirit of systemd-networkd's `MACAddressPolicy=persistent`.
+  ///
+  /// The same inputs always produce the same address, on every platform and
+  /// in every version of this crate. The derivation is:
+  ///
+  /// 1. `h = SipHash-2-4(key, "hardware-address/stable-mac/v1" || for each part: le64(len(part)) || part)`
+  /// 2. The address is the first 6 bytes of `le64(h)`.
+  /// 3. The I/G bit is cleared and the U/L bit is set.
+  ///
+  /// Each part is length-prefixed, so `[b"ab", b"c"]` and `[b"a", b"bc"]`
+  /// derive different addresses.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use hardware_address::MacAddr;
+  ///
+  /// const KEY: [u8; 16] = *b"my-fleet-secret!";
+  ///
+  /// let machine_id = b"b08dfa6083e7567a1921a715000001fb";
+  /// let a = MacAddr::stable_from_seed(&KEY, &[machine_id, b"eth0"]);
+  /// let b = MacAddr::stable_from_seed(&KEY, &[machine_id, b"eth0"]);
+  /// assert_eq!(a, b);
+  /// assert!(a.is_unicast() && a.is_local());
+  /// ```
+  pub const fn stable_from_seed(key: &[u8; 16], parts: &[&[u8]]) -> Self {
+    let mut hasher = SipHasher24::new(key).write(STABLE_MAC_DOMAIN);
+    let mut i = 0;
+    while i < parts.len() {
+      hasher = hasher.write_u64(parts[i].len() as u64).write(parts[i]);
+      i += 1;
+    }
+
+    let h = hasher.finish().to_le_bytes();
+    Self([
+      (h[0] & !crate::__private::IG_BIT) | crate::__private::UL_BIT,
+      h[1],
+      h[2],
+      h[3],
+      h[4],
+      h[5],
+    ])
+  }
+
+  /// Maps the address to its pseudonym under `pseudonymizer`.
+  ///
+  /// See [`Pseudonymizer`] for what is kept from the original address.
+  #[inline]
+  pub const fn pseudonymize(&self, pseudonymizer: &Pseudonymizer) -> Self {
+    Self(pseudonymizer.pseudonymize(&self.0))
+  }
+
+  /// Encrypts the address under `cipher`, keeping its I/G and U/L bits.
+  ///
+  /// See [`AddrCipher`] for details. [`decrypt`](Self::decrypt) with the same
+  /// cipher restores the original address.
+  #[inline]
+  pub const fn encrypt(&self, cipher: &AddrCipher) -> Self {
+    Self(cipher.encrypt(&self.0))
+  }
+
+  /// Decrypts an address produced by [`encrypt`](Self::encrypt) under the
+  /// same `cipher`.
+  #[inline]
+  pub const fn decrypt(&self, cipher: &AddrCipher) -> Self {
+    Self(cipher.decrypt(&self.0))
+  }
+}
+
+/// Domain separation tag for [`MacAddr::stable_from_seed`]. Changing it
+/// changes every derived address, so it is versioned and must stay fixed.
+const STABLE_MAC_DOMAIN: &[u8] = b"hardware-address/stable-mac/v1";
+
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::{ParseError, TestCase};
+  use crate::{Notation, ParseError, TestCase};
 
-  use std::{string::ToString, vec, vec::Vec};
+  use std::{format, string::ToString, vec, vec::Vec};
 
   const MAC_ADDRESS_SIZE: usize = 6;
 
@@ -36,29 +120,55 @@ mod tests {
         output: Some(vec![0xab, 0xcd, 0xef, 0xab, 0xcd, 0xef]),
         err: None,
       },
+      TestCase {
+        input: "00005e005301",
+        output: Some(vec![0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]),
+        err: None,
+      },
+      TestCase {
+        input: "0x00005e005301",
+        output: Some(vec![0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]),
+        err: None,
+      },
+      TestCase {
+        input: "0X00005E005301",
+        output: Some(vec![0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]),
+        err: None,
+      },
       // Invalid MAC-48 cases
       TestCase {
         input: "01.02.03.04.05.06",
         output: None,
-        err: Some(ParseError::InvalidSeparator(b'.')),
+        err: Some(ParseError::invalid_separator(b'.', 2)),
       },
       TestCase {
         input: "01:02:03:04:05:06:",
         output: None,
-        err: Some(ParseError::InvalidLength(18)),
+        err: Some(ParseError::invalid_length(18)),
       },
       TestCase {
         input: "x1:02:03:04:05:06",
         output: None,
-        err: Some(ParseError::InvalidHexDigit([b'x', b'1'])),
+        err: Some(ParseError::invalid_hex_digit(b'x', 0).with_notation(Some(Notation::Colon))),
+      },
+      TestCase {
+        input: "00005e00530g",
+        output: None,
+        err: Some(ParseError::invalid_hex_digit(b'g', 11).with_notation(Some(Notation::BareHex))),
+      },
+      TestCase {
+        input: "0y00005e005301",
+        output: None,
+        err: Some(
+          ParseError::unexpected_separator(b'.', b'0', 4).with_notation(Some(Notation::Dot)),
+        ),
       },
       TestCase {
         input: "01-02:03:04:05:06",
         output: None,
-        err: Some(ParseError::UnexpectedSeparator {
-          expected: b'-',
-          actual: b':',
-        }),
+        err: Some(
+          ParseError::unexpected_separator(b'-', b':', 5).with_notation(Some(Notation::Hyphen)),
+        ),
       },
     ]
   }
@@ -123,6 +233,40 @@ mod tests {
     }
   }
 
+  #[test]
+  fn parse_lenient() {
+    let addr = MacAddr::parse_lenient("0:0:5e:0:53:1\n").unwrap();
+    assert_eq!(addr, MacAddr::try_from("00:00:5e:00:53:01").unwrap());
+    assert!(MacAddr::try_from("0:0:5e:0:53:1").is_err());
+    assert!(MacAddr::parse_lenient("0:0:5e:0:53").is_err());
+  }
+
+  #[test]
+  fn parse_with() {
+    use crate::{Case, ParseOptions};
+
+    let canonical = ParseOptions::COLON.with_case(Some(Case::Lower));
+    let addr = MacAddr::parse_with("00:00:5e:00:53:01", canonical).unwrap();
+    assert_eq!(
+      addr,
+      MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01])
+    );
+    assert!(MacAddr::parse_with("0000.5e00.5301", canonical).is_err());
+    assert!(MacAddr::parse_with("00:00:5E:00:53:01", canonical).is_err());
+  }
+
+  #[test]
+  fn parse_ascii_and_prefix() {
+    let addr = MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]);
+    assert_eq!(MacAddr::parse_ascii(b"00:00:5e:00:53:01"), Ok(addr));
+    assert!(MacAddr::parse_ascii(b"00:00:5e:00:53:01 ").is_err());
+
+    let line = b"lladdr 00:00:5e:00:53:01 REACHABLE";
+    let (parsed, len) = MacAddr::parse_prefix(&line[7..]).unwrap();
+    assert_eq!(parsed, addr);
+    assert_eq!(&line[7 + len..], b" REACHABLE");
+  }
+
   #[test]
   fn test_default() {
     let addr = MacAddr::default();
@@ -146,6 +290,47 @@ mod tests {
     assert_eq!(addr.to_hyphen_separated(), "00-00-5e-00-53-01");
   }
 
+  #[test]
+  fn formatted_upper_and_hex() {
+    let addr = MacAddr::try_from("00:1a:2b:3c:4d:5e").unwrap();
+
+    let colon = addr.to_upper_colon_separated_array();
+    assert_eq!(core::str::from_utf8(&colon).unwrap(), "00:1A:2B:3C:4D:5E");
+    let hyphen = addr.to_upper_hyphen_separated_array();
+    assert_eq!(core::str::from_utf8(&hyphen).unwrap(), "00-1A-2B-3C-4D-5E");
+    let dot = addr.to_upper_dot_separated_array();
+    assert_eq!(core::str::from_utf8(&dot).unwrap(), "001A.2B3C.4D5E");
+    assert_eq!(addr.to_upper_colon_separated(), "00:1A:2B:3C:4D:5E");
+    assert_eq!(addr.to_upper_hyphen_separated(), "00-1A-2B-3C-4D-5E");
+    assert_eq!(addr.to_upper_dot_separated(), "001A.2B3C.4D5E");
+
+    assert_eq!(format!("{addr:x}"), "00:1a:2b:3c:4d:5e");
+    assert_eq!(format!("{addr:X}"), "00:1A:2B:3C:4D:5E");
+    assert_eq!(format!("{addr:#x}"), "0x001a2b3c4d5e");
+    assert_eq!(format!("{addr:#X}"), "0x001A2B3C4D5E");
+    assert_eq!(format!("{addr:>20x}"), "   00:1a:2b:3c:4d:5e");
+
+    let bare = addr.to_bare_hex_array();
+    assert_eq!(core::str::from_utf8(&bare).unwrap(), "001a2b3c4d5e");
+    let bare = addr.to_upper_bare_hex_array();
+    assert_eq!(core::str::from_utf8(&bare).unwrap(), "001A2B3C4D5E");
+    assert_eq!(addr.to_bare_hex(), "001a2b3c4d5e");
+    assert_eq!(addr.to_upper_bare_hex(), "001A2B3C4D5E");
+  }
+
+  #[test]
+  fn display_flags() {
+    let addr = MacAddr::try_from("00:00:5e:00:53:01").unwrap();
+    assert_eq!(format!("{addr:>20}"), "   00:00:5e:00:53:01");
+    assert_eq!(format!("{addr:<20}|"), "00:00:5e:00:53:01   |");
+    assert_eq!(format!("{addr:*^21}"), "**00:00:5e:00:53:01**");
+    assert_eq!(format!("{addr:#}"), "00-00-5e-00-53-01");
+    assert_eq!(format!("{addr:>#18}"), " 00-00-5e-00-53-01");
+    assert_eq!(format!("{addr:?}"), "00:00:5e:00:53:01");
+    assert_eq!(format!("{addr:#?}"), "00:00:5e:00:53:01");
+    assert_eq!(format!("{addr:>18?}"), " 00:00:5e:00:53:01");
+  }
+
   #[cfg(feature = "serde")]
   #[test]
   fn serde_human_readable() {
@@ -285,4 +470,190 @@ mod tests {
     }
     quickcheck::quickcheck(prop as fn(MacAddr) -> bool);
   }
+
+  // ------------------------------------------------------------------
+  // `rand` support
+  // ------------------------------------------------------------------
+
+  #[cfg(feature = "rand")]
+  #[test]
+  fn rand_flag_bits() {
+    use rand::rngs::mock::StepRng;
+
+    let mut rng = StepRng::new(0x0123_4567_89ab_cdef, 0x9e37_79b9_7f4a_7c15);
+    for _ in 0..256 {
+      let addr = MacAddr::random_local_unicast(&mut rng);
+      assert!(addr.is_unicast() && addr.is_local(), "{addr}");
+
+      let addr = MacAddr::random_local_multicast(&mut rng);
+      assert!(addr.is_multicast() && addr.is_local(), "{addr}");
+
+      assert!(MacAddr::random_unicast(&mut rng).is_unicast());
+      assert!(MacAddr::random_multicast(&mut rng).is_multicast());
+    }
+  }
+
+  #[cfg(feature = "rand")]
+  #[test]
+  fn rand_with_prefix() {
+    use rand::rngs::mock::StepRng;
+
+    let mut rng = StepRng::new(0xdead_beef, 0x9e37_79b9_7f4a_7c15);
+    let addr = MacAddr::random_with_prefix(&mut rng, &[0x00, 0x00, 0x5e]);
+    assert_eq!(&addr.octets()[..3], &[0x00, 0x00, 0x5e]);
+    assert!(addr.is_unicast() && addr.is_universal());
+
+    let full = [0x02, 0x11, 0x22, 0x33, 0x44, 0x55];
+    assert_eq!(MacAddr::random_with_prefix(&mut rng, &full).octets(), full);
+  }
+
+  #[cfg(feature = "rand")]
+  #[test]
+  #[should_panic]
+  fn rand_with_prefix_too_long() {
+    use rand::rngs::mock::StepRng;
+
+    let mut rng = StepRng::new(0, 1);
+    let _ = MacAddr::random_with_prefix(&mut rng, &[0; MAC_ADDRESS_SIZE + 1]);
+  }
+
+  #[cfg(feature = "rand")]
+  #[test]
+  fn rand_distribution() {
+    use rand::{rngs::mock::StepRng, Rng};
+
+    let mut a = StepRng::new(7, 11);
+    let mut b = StepRng::new(7, 11);
+    let sampled: MacAddr = a.gen();
+    assert_eq!(sampled, MacAddr::random(&mut b));
+  }
+
+  /// Pinned outputs of `stable_from_seed`. These must never change: a
+  /// different value here means every persistent address derived by a
+  /// previous release would change too.
+  #[test]
+  fn stable_from_seed_vectors() {
+    const KEY: [u8; 16] = [
+      0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+      0x0f,
+    ];
+    const MACHINE_ID: &[u8] = b"b08dfa6083e7567a1921a715000001fb";
+
+    let cases: [(&[&[u8]], &str); 4] = [
+      (&[], "5e:0e:22:62:84:bc"),
+      (&[b"eth0"], "82:56:1a:6d:40:37"),
+      (&[MACHINE_ID, b"eth0"], "12:ac:86:ac:1a:5a"),
+      (&[MACHINE_ID, b"eth1"], "ee:57:ab:21:4a:75"),
+    ];
+    for (parts, expected) in cases {
+      let addr = MacAddr::stable_from_seed(&KEY, parts);
+      assert_eq!(addr.to_string(), expected);
+      assert!(addr.is_unicast() && addr.is_local());
+    }
+
+    // Length prefixes keep part boundaries significant.
+    assert_ne!(
+      MacAddr::stable_from_seed(&KEY, &[b"ab", b"c"]),
+      MacAddr::stable_from_seed(&KEY, &[b"a", b"bc"]),
+    );
+
+    // Usable in const context.
+    const ADDR: MacAddr = MacAddr::stable_from_seed(&KEY, &[b"eth0"]);
+    assert_eq!(ADDR.to_string(), "82:56:1a:6d:40:37");
+  }
+
+  /// Pinned pseudonyms: joining datasets across releases relies on these
+  /// staying the same.
+  #[test]
+  fn pseudonymize_vectors() {
+    let addr = MacAddr::from_raw([0x00, 0x1b, 0x21, 0x3a, 0x4c, 0x5d]);
+    let p = Pseudonymizer::new(*b"0123456789abcdef");
+    assert_eq!(addr.pseudonymize(&p).to_string(), "68:14:68:43:1e:fc");
+    assert_eq!(
+      addr.pseudonymize(&p.with_keep_oui(true)).to_string(),
+      "00:1b:21:43:1e:fc"
+    );
+
+    let multicast = MacAddr::from_raw([0x01, 0x00, 0x5e, 0x00, 0x00, 0xfb]);
+    assert!(multicast.pseudonymize(&p).is_multicast());
+  }
+
+  /// Pinned ciphertexts: tokens issued by a previous release must still
+  /// decrypt.
+  #[test]
+  fn encrypt_vectors() {
+    let addr = MacAddr::from_raw([0x00, 0x1b, 0x21, 0x3a, 0x4c, 0x5d]);
+    let cipher = AddrCipher::new(*b"0123456789abcdef");
+
+    let token = addr.encrypt(&cipher);
+    assert_eq!(token.to_string(), "d4:32:1a:1c:db:74");
+    assert_eq!(token.decrypt(&cipher), addr);
+
+    let token = addr.encrypt(&cipher.with_keep_oui(true));
+    assert_eq!(token.to_string(), "00:1b:21:78:89:b6");
+    assert_eq!(token.decrypt(&cipher.with_keep_oui(true)), addr);
+  }
+
+  #[test]
+  fn flag_bits() {
+    let addr = MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]);
+    assert!(addr.is_unicast() && addr.is_universal());
+    assert!(!addr.is_multicast() && !addr.is_local());
+
+    let addr = MacAddr::from_raw([0x03, 0x00, 0x5e, 0x00, 0x53, 0x01]);
+    assert!(addr.is_multicast() && addr.is_local());
+  }
+
+  // ------------------------------------------------------------------
+  // Zero-copy views
+  // ------------------------------------------------------------------
+
+  #[test]
+  fn byte_views() {
+    // An Ethernet header: destination, source, EtherType.
+    let frame = [
+      0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x5e, 0x00, 0x53, 0x01, 0x08, 0x06,
+    ];
+    let dst = MacAddr::ref_from_bytes(&frame[..6]).unwrap();
+    assert!(dst.is_multicast());
+    let addrs = MacAddr::slice_from_bytes(&frame[..12]).unwrap();
+    assert_eq!(addrs.len(), 2);
+    assert_eq!(addrs[1].to_string(), "00:00:5e:00:53:01");
+    assert!(MacAddr::slice_from_bytes(&[]).unwrap().is_empty());
+
+    let err = MacAddr::ref_from_bytes(&frame[..7]).unwrap_err();
+    assert_eq!((err.size(), err.actual()), (6, 7));
+    assert!(!err.is_multiple());
+    assert!(MacAddr::slice_from_bytes(&frame).unwrap_err().is_multiple());
+
+    assert_eq!(MacAddr::try_from(&frame[6..12]).unwrap(), addrs[1]);
+    assert!(MacAddr::try_from(&frame[..]).is_err());
+
+    let mut addr = MacAddr::new();
+    addr.as_mut()[0] = 0x02;
+    assert!(addr.is_local());
+  }
+
+  #[cfg(feature = "zerocopy")]
+  #[test]
+  fn zerocopy_views() {
+    use zerocopy::{FromBytes, IntoBytes};
+
+    let bytes = [0x00, 0x00, 0x5e, 0x00, 0x53, 0x01, 0x02];
+    let (addr, rest) = MacAddr::ref_from_prefix(&bytes).unwrap();
+    assert_eq!(addr.octets(), [0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]);
+    assert_eq!(rest, [0x02]);
+    assert_eq!(addr.as_bytes(), IntoBytes::as_bytes(addr));
+    assert_eq!(<[MacAddr]>::ref_from_bytes(&bytes[..6]).unwrap().len(), 1);
+  }
+
+  #[cfg(feature = "bytemuck")]
+  #[test]
+  fn bytemuck_casts() {
+    let addrs = [MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]); 2];
+    let bytes: &[u8] = bytemuck::cast_slice(&addrs);
+    assert_eq!(bytes.len(), 12);
+    assert_eq!(bytemuck::cast_slice::<u8, MacAddr>(bytes), addrs);
+    assert_eq!(<MacAddr as bytemuck::Zeroable>::zeroed(), MacAddr::new());
+  }
 }
@@ -0,0 +1,328 @@
+//! IPv6 Neighbor Discovery ([RFC 4861]) Source and Target Link-Layer
+//! Address options.
+//!
+//! The size of the option depends on the link: 8 bytes for Ethernet
+//! ([RFC 2464]), 16 bytes for EUI-64 links such as IEEE 802.15.4
+//! ([RFC 4944]) and 24 bytes for IP over InfiniBand ([RFC 4391]), with the
+//! address padded to a multiple of 8 bytes.
+//!
+//! [RFC 4861]: https://www.rfc-editor.org/rfc/rfc4861#section-4.6.1
+//! [RFC 2464]: https://www.rfc-editor.org/rfc/rfc2464#section-6
+//! [RFC 4944]: https://www.rfc-editor.org/rfc/rfc4944#section-8
+//! [RFC 4391]: https://www.rfc-editor.org/rfc/rfc4391#section-9.2
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hardware_address::{
+//!   ndp::{LinkLayerAddrOption, LinkLayerOptionKind},
+//!   MacAddr,
+//! };
+//!
+//! let mac = MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]);
+//! let option = LinkLayerAddrOption::new(LinkLayerOptionKind::Source, mac.into());
+//!
+//! let mut buf = [0u8; 8];
+//! assert_eq!(option.encode(&mut buf), Ok(8));
+//! assert_eq!(buf, [0x01, 0x01, 0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]);
+//! assert_eq!(LinkLayerAddrOption::decode(&buf), Ok(option));
+//! ```
+
+use crate::AnyHardwareAddr;
+
+/// An error returned while decoding or encoding a link-layer address
+/// option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+pub enum NdpError {
+  /// The buffer is too short for the option.
+  #[error("buffer too short: expected at least {expected} bytes, but got {actual} bytes")]
+  TooShort {
+    /// The number of bytes the option needs.
+    expected: usize,
+    /// The length of the buffer.
+    actual: usize,
+  },
+  /// The option is not a Source or Target Link-Layer Address option.
+  #[error("invalid option type {0}, expected 1 or 2")]
+  InvalidType(u8),
+  /// The option length, in units of 8 bytes, is zero or does not match a
+  /// supported link-layer address.
+  #[error("invalid option length of {0} units of 8 bytes")]
+  InvalidLength(u8),
+}
+
+/// Whether a link-layer address option carries the source or the target
+/// address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinkLayerOptionKind {
+  /// Source Link-Layer Address, option type 1.
+  Source,
+  /// Target Link-Layer Address, option type 2.
+  Target,
+}
+
+impl LinkLayerOptionKind {
+  /// Returns the kind of option type `value`, if it is a link-layer
+  /// address option.
+  #[inline]
+  pub const fn from_u8(value: u8) -> Option<Self> {
+    match value {
+      1 => Some(Self::Source),
+      2 => Some(Self::Target),
+      _ => None,
+    }
+  }
+
+  /// Returns the option type.
+  #[inline]
+  pub const fn to_u8(self) -> u8 {
+    match self {
+      Self::Source => 1,
+      Self::Target => 2,
+    }
+  }
+}
+
+/// A Source or Target Link-Layer Address option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LinkLayerAddrOption {
+  kind: LinkLayerOptionKind,
+  addr: AnyHardwareAddr,
+}
+
+impl LinkLayerAddrOption {
+  /// Creates an option carrying `addr`.
+  #[inline]
+  pub const fn new(kind: LinkLayerOptionKind, addr: AnyHardwareAddr) -> Self {
+    Self { kind, addr }
+  }
+
+  /// Returns whether the option carries the source or the target address.
+  #[inline]
+  pub const fn kind(&self) -> LinkLayerOptionKind {
+    self.kind
+  }
+
+  /// Returns the link-layer address.
+  #[inline]
+  pub const fn addr(&self) -> AnyHardwareAddr {
+    self.addr
+  }
+
+  /// Returns the length of the encoded option in bytes, a multiple of 8.
+  #[inline]
+  pub const fn encoded_len(&self) -> usize {
+    Self::units(&self.addr) as usize * 8
+  }
+
+  /// Returns the option length, in units of 8 bytes, for `addr`.
+  #[inline]
+  const fn units(addr: &AnyHardwareAddr) -> u8 {
+    match addr {
+      AnyHardwareAddr::Mac(_) => 1,
+      AnyHardwareAddr::Eui64(_) => 2,
+      AnyHardwareAddr::InfiniBand(_) => 3,
+    }
+  }
+
+  /// Returns the offset of the address in an option of length `units`.
+  /// IPoIB options have two reserved bytes before the address.
+  #[inline]
+  const fn addr_offset(units: u8) -> usize {
+    match units {
+      3 => 4,
+      _ => 2,
+    }
+  }
+
+  /// Decodes the option at the start of `src`.
+  ///
+  /// The option length selects the address type: 1 for a `MacAddr`, 2 for
+  /// an `Eui64Addr` and 3 for an `InfiniBandAddr`. Padding and reserved
+  /// bytes are ignored, as RFC 4861 requires.
+  pub fn decode(src: &[u8]) -> Result<Self, NdpError> {
+    if src.len() < 2 {
+      return Err(NdpError::TooShort {
+        expected: 2,
+        actual: src.len(),
+      });
+    }
+    let kind = LinkLayerOptionKind::from_u8(src[0]).ok_or(NdpError::InvalidType(src[0]))?;
+    let units = src[1];
+    let size = match units {
+      1 => 6,
+      2 => 8,
+      3 => 20,
+      _ => return Err(NdpError::InvalidLength(units)),
+    };
+    let expected = units as usize * 8;
+    if src.len() < expected {
+      return Err(NdpError::TooShort {
+        expected,
+        actual: src.len(),
+      });
+    }
+    let at = Self::addr_offset(units);
+    match AnyHardwareAddr::from_bytes(&src[at..at + size]) {
+      Some(addr) => Ok(Self { kind, addr }),
+      None => Err(NdpError::InvalidLength(units)),
+    }
+  }
+
+  /// Finds the first option of `kind` in the options area of a Neighbor
+  /// Discovery message, skipping other options.
+  ///
+  /// Fails if an option before it is malformed: it has length zero or
+  /// runs past the end of `options`.
+  pub fn find(options: &[u8], kind: LinkLayerOptionKind) -> Result<Option<Self>, NdpError> {
+    let mut rest = options;
+    while !rest.is_empty() {
+      if rest.len() < 2 {
+        return Err(NdpError::TooShort {
+          expected: 2,
+          actual: rest.len(),
+        });
+      }
+      let len = rest[1] as usize * 8;
+      if len == 0 {
+        return Err(NdpError::InvalidLength(0));
+      }
+      if rest.len() < len {
+        return Err(NdpError::TooShort {
+          expected: len,
+          actual: rest.len(),
+        });
+      }
+      if rest[0] == kind.to_u8() {
+        return Self::decode(&rest[..len]).map(Some);
+      }
+      rest = &rest[len..];
+    }
+    Ok(None)
+  }
+
+  /// Encodes the option to the start of `buf`, zeroing padding and reserved
+  /// bytes, and returns its length.
+  pub fn encode(&self, buf: &mut [u8]) -> Result<usize, NdpError> {
+    let units = Self::units(&self.addr);
+    let len = units as usize * 8;
+    if buf.len() < len {
+      return Err(NdpError::TooShort {
+        expected: len,
+        actual: buf.len(),
+      });
+    }
+    let buf = &mut buf[..len];
+    buf.fill(0);
+    buf[0] = self.kind.to_u8();
+    buf[1] = units;
+    let at = Self::addr_offset(units);
+    buf[at..at + self.addr.size()].copy_from_slice(self.addr.as_bytes());
+    Ok(len)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{Eui64Addr, InfiniBandAddr, MacAddr};
+
+  #[test]
+  fn round_trips() {
+    let ib = InfiniBandAddr::from_raw([
+      0x00, 0x00, 0x00, 0x48, 0xfe, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0xc9,
+      0x03, 0x00, 0x0a, 0x0b, 0x0c,
+    ]);
+    let cases: [(AnyHardwareAddr, usize, usize); 3] = [
+      (MacAddr::from_raw([2, 0, 0x5e, 0, 0x53, 1]).into(), 8, 2),
+      (
+        Eui64Addr::from_raw([2, 0, 0x5e, 0x10, 0, 0, 0, 1]).into(),
+        16,
+        2,
+      ),
+      (ib.into(), 24, 4),
+    ];
+    for (addr, len, at) in cases {
+      let option = LinkLayerAddrOption::new(LinkLayerOptionKind::Target, addr);
+      assert_eq!(option.encoded_len(), len);
+
+      let mut buf = [0xff; 32];
+      assert_eq!(option.encode(&mut buf), Ok(len));
+      assert_eq!(buf[..2], [2, (len / 8) as u8]);
+      assert!(buf[2..at].iter().all(|&b| b == 0));
+      assert_eq!(&buf[at..at + addr.size()], addr.as_bytes());
+      assert!(buf[at + addr.size()..len].iter().all(|&b| b == 0));
+      assert_eq!(buf[len], 0xff);
+
+      assert_eq!(LinkLayerAddrOption::decode(&buf[..len]), Ok(option));
+    }
+  }
+
+  #[test]
+  fn find() {
+    // A nonce option (type 14, length 1) before a source link-layer
+    // address option.
+    let options = [
+      14, 1, 1, 2, 3, 4, 5, 6, 1, 1, 0x00, 0x00, 0x5e, 0x00, 0x53, 0x01,
+    ];
+    let found = LinkLayerAddrOption::find(&options, LinkLayerOptionKind::Source)
+      .unwrap()
+      .unwrap();
+    assert_eq!(
+      found.addr(),
+      MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]).into()
+    );
+    assert_eq!(
+      LinkLayerAddrOption::find(&options, LinkLayerOptionKind::Target),
+      Ok(None)
+    );
+    assert_eq!(
+      LinkLayerAddrOption::find(&[14, 0, 0, 0], LinkLayerOptionKind::Source),
+      Err(NdpError::InvalidLength(0))
+    );
+    assert_eq!(
+      LinkLayerAddrOption::find(&options[..12], LinkLayerOptionKind::Source),
+      Err(NdpError::TooShort {
+        expected: 8,
+        actual: 4
+      })
+    );
+  }
+
+  #[test]
+  fn malformed() {
+    assert_eq!(
+      LinkLayerAddrOption::decode(&[1]),
+      Err(NdpError::TooShort {
+        expected: 2,
+        actual: 1
+      })
+    );
+    assert_eq!(
+      LinkLayerAddrOption::decode(&[3, 1, 0, 0, 0, 0, 0, 0]),
+      Err(NdpError::InvalidType(3))
+    );
+    for units in [0, 4] {
+      assert_eq!(
+        LinkLayerAddrOption::decode(&[1, units, 0, 0, 0, 0, 0, 0]),
+        Err(NdpError::InvalidLength(units))
+      );
+    }
+    assert_eq!(
+      LinkLayerAddrOption::decode(&[1, 3, 0, 0, 0, 0, 0, 0]),
+      Err(NdpError::TooShort {
+        expected: 24,
+        actual: 8
+      })
+    );
+
+    let option = LinkLayerAddrOption::new(LinkLayerOptionKind::Source, MacAddr::new().into());
+    assert_eq!(
+      option.encode(&mut [0; 7]),
+      Err(NdpError::TooShort {
+        expected: 8,
+        actual: 7
+      })
+    );
+  }
+}
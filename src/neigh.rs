@@ -0,0 +1,665 @@
+//! Neighbor table parsers for `/proc/net/arp`, `ip neigh show` text and, with
+//! the `serde` feature, `ip -j neigh show` JSON.
+//!
+//! Incomplete and failed entries have no link-layer address and are kept
+//! with [`hardware_addr`](NeighborEntry::hardware_addr) `None`. `ip neigh`
+//! reports MAC and 20-byte IP over InfiniBand addresses, `/proc/net/arp`
+//! only MAC addresses.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hardware_address::{
+//!   neigh::{parse_ip_neigh, NeighborFlags, NeighborState},
+//!   MacAddr,
+//! };
+//!
+//! let entries = parse_ip_neigh(
+//!   "192.0.2.1 dev eth0 lladdr 00:00:5e:00:53:01 router REACHABLE\n\
+//!    192.0.2.7 dev eth0 FAILED\n",
+//! )
+//! .unwrap();
+//! assert_eq!(entries[0].ip().to_string(), "192.0.2.1");
+//! assert_eq!(
+//!   entries[0].mac_addr(),
+//!   Some(MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]))
+//! );
+//! assert!(entries[0].flags().contains(NeighborFlags::ROUTER));
+//! assert_eq!(entries[1].hardware_addr(), None);
+//! assert_eq!(entries[1].state(), Some(NeighborState::Failed));
+//! ```
+
+use core::ops::{BitOr, BitOrAssign};
+use std::{net::IpAddr, string::String, vec::Vec};
+
+use crate::{hex::decode_hex, AnyHardwareAddr, MacAddr};
+
+/// An error returned while parsing a neighbor table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, thiserror::Error)]
+pub enum NeighborParseError {
+  /// A required field is missing.
+  #[error("line {line}: missing {field}")]
+  MissingField {
+    /// The 1-based line number.
+    line: usize,
+    /// The name of the field.
+    field: &'static str,
+  },
+  /// A field is malformed.
+  #[error("line {line}: invalid {field}")]
+  InvalidField {
+    /// The 1-based line number.
+    line: usize,
+    /// The name of the field.
+    field: &'static str,
+  },
+}
+
+/// The state of a neighbor entry (`NUD_*`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum NeighborState {
+  /// Address resolution is in progress.
+  Incomplete,
+  /// The neighbor was recently confirmed reachable.
+  Reachable,
+  /// The neighbor has not been confirmed recently.
+  Stale,
+  /// Waiting before probing a stale neighbor.
+  Delay,
+  /// Probing a stale neighbor.
+  Probe,
+  /// Address resolution failed.
+  Failed,
+  /// The device does not need address resolution.
+  NoArp,
+  /// A static entry.
+  Permanent,
+  /// No state.
+  None,
+}
+
+impl NeighborState {
+  /// Returns the state with `NUD_*` value `value`, if it is a single state.
+  #[inline]
+  pub const fn from_u16(value: u16) -> Option<Self> {
+    Some(match value {
+      0x01 => Self::Incomplete,
+      0x02 => Self::Reachable,
+      0x04 => Self::Stale,
+      0x08 => Self::Delay,
+      0x10 => Self::Probe,
+      0x20 => Self::Failed,
+      0x40 => Self::NoArp,
+      0x80 => Self::Permanent,
+      0x00 => Self::None,
+      _ => return None,
+    })
+  }
+
+  /// Returns the `NUD_*` value of the state.
+  #[inline]
+  pub const fn to_u16(self) -> u16 {
+    match self {
+      Self::Incomplete => 0x01,
+      Self::Reachable => 0x02,
+      Self::Stale => 0x04,
+      Self::Delay => 0x08,
+      Self::Probe => 0x10,
+      Self::Failed => 0x20,
+      Self::NoArp => 0x40,
+      Self::Permanent => 0x80,
+      Self::None => 0x00,
+    }
+  }
+
+  /// Returns the state named `name`, as `ip neigh` prints it.
+  #[inline]
+  pub fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "INCOMPLETE" => Self::Incomplete,
+      "REACHABLE" => Self::Reachable,
+      "STALE" => Self::Stale,
+      "DELAY" => Self::Delay,
+      "PROBE" => Self::Probe,
+      "FAILED" => Self::Failed,
+      "NOARP" => Self::NoArp,
+      "PERMANENT" => Self::Permanent,
+      "NONE" => Self::None,
+      _ => return None,
+    })
+  }
+
+  /// Returns the name of the state, as `ip neigh` prints it.
+  #[inline]
+  pub const fn as_str(&self) -> &'static str {
+    match self {
+      Self::Incomplete => "INCOMPLETE",
+      Self::Reachable => "REACHABLE",
+      Self::Stale => "STALE",
+      Self::Delay => "DELAY",
+      Self::Probe => "PROBE",
+      Self::Failed => "FAILED",
+      Self::NoArp => "NOARP",
+      Self::Permanent => "PERMANENT",
+      Self::None => "NONE",
+    }
+  }
+}
+
+/// Neighbor entry flags (`NTF_*`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct NeighborFlags(u8);
+
+impl NeighborFlags {
+  /// The entry is a proxy entry, published for another host.
+  pub const PROXY: Self = Self(0x08);
+  /// The entry was learned by an external control plane.
+  pub const EXTERN_LEARN: Self = Self(0x10);
+  /// The entry is offloaded to hardware.
+  pub const OFFLOAD: Self = Self(0x20);
+  /// The neighbor is an IPv6 router.
+  pub const ROUTER: Self = Self(0x80);
+
+  /// Returns the empty set of flags.
+  #[inline]
+  pub const fn empty() -> Self {
+    Self(0)
+  }
+
+  /// Returns the flags with `NTF_*` bits `bits`.
+  #[inline]
+  pub const fn from_bits(bits: u8) -> Self {
+    Self(bits)
+  }
+
+  /// Returns the `NTF_*` bits.
+  #[inline]
+  pub const fn bits(&self) -> u8 {
+    self.0
+  }
+
+  /// Returns `true` if no flag is set.
+  #[inline]
+  pub const fn is_empty(&self) -> bool {
+    self.0 == 0
+  }
+
+  /// Returns `true` if every flag of `other` is set.
+  #[inline]
+  pub const fn contains(&self, other: Self) -> bool {
+    self.0 & other.0 == other.0
+  }
+
+  /// Returns the flags set in either `self` or `other`.
+  #[inline]
+  pub const fn union(self, other: Self) -> Self {
+    Self(self.0 | other.0)
+  }
+
+  /// Returns the flag named `name`, as `ip neigh` prints it.
+  #[inline]
+  fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "proxy" => Self::PROXY,
+      "extern_learn" => Self::EXTERN_LEARN,
+      "offload" => Self::OFFLOAD,
+      "router" => Self::ROUTER,
+      _ => return None,
+    })
+  }
+}
+
+impl BitOr for NeighborFlags {
+  type Output = Self;
+
+  #[inline]
+  fn bitor(self, rhs: Self) -> Self {
+    self.union(rhs)
+  }
+}
+
+impl BitOrAssign for NeighborFlags {
+  #[inline]
+  fn bitor_assign(&mut self, rhs: Self) {
+    *self = self.union(rhs);
+  }
+}
+
+/// An entry of a neighbor table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NeighborEntry {
+  ip: IpAddr,
+  hw: Option<AnyHardwareAddr>,
+  dev: Option<String>,
+  state: Option<NeighborState>,
+  flags: NeighborFlags,
+}
+
+impl NeighborEntry {
+  /// Returns the IP address of the neighbor.
+  #[inline]
+  pub const fn ip(&self) -> IpAddr {
+    self.ip
+  }
+
+  /// Returns the link-layer address of the neighbor, or `None` if it is not
+  /// resolved or not of a supported size.
+  #[inline]
+  pub const fn hardware_addr(&self) -> Option<AnyHardwareAddr> {
+    self.hw
+  }
+
+  /// Returns the link-layer address of the neighbor if it is a MAC address.
+  #[inline]
+  pub const fn mac_addr(&self) -> Option<MacAddr> {
+    match self.hw {
+      Some(AnyHardwareAddr::Mac(addr)) => Some(addr),
+      _ => None,
+    }
+  }
+
+  /// Returns the device the neighbor is on, or `None` if the source does
+  /// not report it.
+  ///
+  /// `ip neigh show dev <dev>` leaves the device out of every entry.
+  #[inline]
+  pub fn dev(&self) -> Option<&str> {
+    self.dev.as_deref()
+  }
+
+  /// Returns the state of the entry, or `None` if the source does not
+  /// report it.
+  ///
+  /// `/proc/net/arp` only tells permanent and incomplete entries apart from
+  /// the rest.
+  #[inline]
+  pub const fn state(&self) -> Option<NeighborState> {
+    self.state
+  }
+
+  /// Returns the entry flags.
+  #[inline]
+  pub const fn flags(&self) -> NeighborFlags {
+    self.flags
+  }
+}
+
+/// `ATF_COM`: the entry is complete.
+const ATF_COM: u32 = 0x02;
+/// `ATF_PERM`: the entry is permanent.
+const ATF_PERM: u32 = 0x04;
+/// `ATF_PUBL`: the entry is published (proxy ARP).
+const ATF_PUBL: u32 = 0x08;
+
+/// Parses the contents of `/proc/net/arp`.
+///
+/// The header line and blank lines are skipped.
+///
+/// The kernel prints at most 9 octets of an address here (`HBUFFERLEN` in
+/// `net/ipv4/arp.c`), so IP over InfiniBand entries come out truncated and
+/// have [`hardware_addr`](NeighborEntry::hardware_addr) `None`. Use
+/// [`parse_ip_neigh`] or netlink to get those addresses.
+pub fn parse_proc_net_arp(text: &str) -> Result<Vec<NeighborEntry>, NeighborParseError> {
+  let mut entries = Vec::new();
+  for (i, line) in text.lines().enumerate() {
+    let line_no = i + 1;
+    if line.trim().is_empty() || line.starts_with("IP address") {
+      continue;
+    }
+    let missing = |field| NeighborParseError::MissingField {
+      line: line_no,
+      field,
+    };
+    let invalid = |field| NeighborParseError::InvalidField {
+      line: line_no,
+      field,
+    };
+
+    let mut fields = line.split_whitespace();
+    let ip = fields
+      .next()
+      .ok_or_else(|| missing("IP address"))?
+      .parse()
+      .map_err(|_| invalid("IP address"))?;
+    fields.next().ok_or_else(|| missing("HW type"))?;
+    let flags = fields.next().ok_or_else(|| missing("flags"))?;
+    let flags =
+      u32::from_str_radix(flags.trim_start_matches("0x"), 16).map_err(|_| invalid("flags"))?;
+    let hw = fields.next().ok_or_else(|| missing("HW address"))?;
+    let hw = parse_lladdr(hw).ok_or_else(|| invalid("HW address"))?;
+    fields.next().ok_or_else(|| missing("mask"))?;
+    let dev = fields.next().ok_or_else(|| missing("device"))?;
+
+    let complete = flags & ATF_COM != 0;
+    let state = if flags & ATF_PERM != 0 {
+      Some(NeighborState::Permanent)
+    } else if !complete {
+      Some(NeighborState::Incomplete)
+    } else {
+      None
+    };
+    entries.push(NeighborEntry {
+      ip,
+      hw: if complete { hw } else { None },
+      dev: Some(dev.into()),
+      state,
+      flags: if flags & ATF_PUBL != 0 {
+        NeighborFlags::PROXY
+      } else {
+        NeighborFlags::empty()
+      },
+    });
+  }
+  Ok(entries)
+}
+
+/// Parses the output of `ip neigh show`, one entry per line.
+///
+/// Unknown keywords, such as those `ip -s` adds, are skipped. An
+/// `(incomplete)` address is treated as no address. The device is optional,
+/// as the output of `ip neigh show dev <dev>` does not repeat it.
+pub fn parse_ip_neigh(text: &str) -> Result<Vec<NeighborEntry>, NeighborParseError> {
+  let mut entries = Vec::new();
+  for (i, line) in text.lines().enumerate() {
+    let line_no = i + 1;
+    if line.trim().is_empty() {
+      continue;
+    }
+    let missing = |field| NeighborParseError::MissingField {
+      line: line_no,
+      field,
+    };
+    let invalid = |field| NeighborParseError::InvalidField {
+      line: line_no,
+      field,
+    };
+
+    let mut tokens = line.split_whitespace();
+    let ip = tokens
+      .next()
+      .ok_or_else(|| missing("address"))?
+      .parse()
+      .map_err(|_| invalid("address"))?;
+    let mut dev = None;
+    let mut hw = None;
+    let mut state = None;
+    let mut flags = NeighborFlags::empty();
+    while let Some(token) = tokens.next() {
+      match token {
+        "dev" => dev = Some(tokens.next().ok_or_else(|| missing("dev"))?),
+        "lladdr" => {
+          let lladdr = tokens.next().ok_or_else(|| missing("lladdr"))?;
+          if lladdr != "(incomplete)" {
+            hw = parse_lladdr(lladdr).ok_or_else(|| invalid("lladdr"))?;
+          }
+        }
+        "(incomplete)" => {}
+        _ => {
+          if let Some(flag) = NeighborFlags::from_name(token) {
+            flags |= flag;
+          } else if state.is_none() {
+            state = NeighborState::from_name(token);
+          }
+        }
+      }
+    }
+    entries.push(NeighborEntry {
+      ip,
+      hw,
+      dev: dev.map(Into::into),
+      state,
+      flags,
+    });
+  }
+  Ok(entries)
+}
+
+/// Parses a colon-separated link-layer address, returning `Some(None)` if
+/// it is well formed but not of a supported size.
+fn parse_lladdr(text: &str) -> Option<Option<AnyHardwareAddr>> {
+  let mut buf = [0u8; 32];
+  decode_hex(text, &mut buf)
+    .ok()
+    .map(|len| AnyHardwareAddr::from_bytes(&buf[..len]))
+}
+
+/// Deserializes an element of the array `ip -j neigh show` prints.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for NeighborEntry {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: serde::Deserializer<'de>,
+  {
+    use serde::de::{Error, IgnoredAny};
+
+    /// Deserializes a key that `ip -j` sets to `null` when a flag is set.
+    fn present<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+      <IgnoredAny as serde::Deserialize>::deserialize(deserializer).map(|_| true)
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Record {
+      dst: String,
+      #[serde(default)]
+      dev: Option<String>,
+      #[serde(default)]
+      lladdr: Option<String>,
+      #[serde(default)]
+      state: Vec<String>,
+      #[serde(default, deserialize_with = "present")]
+      proxy: bool,
+      #[serde(default, deserialize_with = "present")]
+      extern_learn: bool,
+      #[serde(default, deserialize_with = "present")]
+      offload: bool,
+      #[serde(default, deserialize_with = "present")]
+      router: bool,
+    }
+
+    let record = <Record as serde::Deserialize>::deserialize(deserializer)?;
+    let ip = record
+      .dst
+      .parse()
+      .map_err(|_| D::Error::custom("invalid dst"))?;
+    let hw = match record.lladdr {
+      Some(lladdr) => parse_lladdr(&lladdr).ok_or_else(|| D::Error::custom("invalid lladdr"))?,
+      None => None,
+    };
+    let mut flags = NeighborFlags::empty();
+    for (set, flag) in [
+      (record.proxy, NeighborFlags::PROXY),
+      (record.extern_learn, NeighborFlags::EXTERN_LEARN),
+      (record.offload, NeighborFlags::OFFLOAD),
+      (record.router, NeighborFlags::ROUTER),
+    ] {
+      if set {
+        flags |= flag;
+      }
+    }
+    Ok(Self {
+      ip,
+      hw,
+      dev: record.dev,
+      state: record
+        .state
+        .iter()
+        .find_map(|name| NeighborState::from_name(name)),
+      flags,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::InfiniBandAddr;
+
+  const IB: &str = "80:00:02:08:fe:80:00:00:00:00:00:00:00:02:c9:03:00:0a:0b:0c";
+
+  fn mac() -> Option<AnyHardwareAddr> {
+    Some(MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]).into())
+  }
+
+  fn ib() -> Option<AnyHardwareAddr> {
+    Some(IB.parse::<InfiniBandAddr>().unwrap().into())
+  }
+
+  #[test]
+  fn proc_net_arp() {
+    let text = "IP address       HW type     Flags       HW address            Mask     Device\n\
+       192.0.2.1        0x1         0x2         00:00:5e:00:53:01     *        eth0\n\
+       192.0.2.9        0x1         0x0         00:00:00:00:00:00     *        eth0\n\
+       192.0.2.10       0x1         0xc         00:00:5e:00:53:01     *        eth0\n\
+       198.51.100.2     0x20        0x2         80:00:02:08:fe:80:00:00:00     *        ib0\n";
+    let entries = parse_proc_net_arp(text).unwrap();
+    assert_eq!(entries.len(), 4);
+
+    assert_eq!(entries[0].ip(), "192.0.2.1".parse::<IpAddr>().unwrap());
+    assert_eq!(entries[0].hardware_addr(), mac());
+    assert_eq!(entries[0].dev(), Some("eth0"));
+    assert_eq!(entries[0].state(), None);
+
+    assert_eq!(entries[1].hardware_addr(), None);
+    assert_eq!(entries[1].state(), Some(NeighborState::Incomplete));
+
+    assert_eq!(entries[2].state(), Some(NeighborState::Permanent));
+    assert_eq!(entries[2].flags(), NeighborFlags::PROXY);
+
+    // The kernel truncates the IPoIB address to 9 octets.
+    assert_eq!(entries[3].hardware_addr(), None);
+    assert_eq!(entries[3].state(), None);
+    assert_eq!(entries[3].dev(), Some("ib0"));
+
+    assert_eq!(
+      parse_proc_net_arp("192.0.2.1 0x1 0x2 00:00:5e:00:53:01 *\n"),
+      Err(NeighborParseError::MissingField {
+        line: 1,
+        field: "device"
+      })
+    );
+    assert_eq!(
+      parse_proc_net_arp("IP address\n192.0.2.1 0x1 0x2 zz *  eth0\n"),
+      Err(NeighborParseError::InvalidField {
+        line: 2,
+        field: "HW address"
+      })
+    );
+  }
+
+  #[test]
+  fn ip_neigh() {
+    let text = std::format!(
+      "192.0.2.1 dev eth0 lladdr 00:00:5e:00:53:01 REACHABLE\n\
+       fe80::1 dev eth0 lladdr 00:00:5e:00:53:01 router STALE\n\
+       192.0.2.7 dev eth0  FAILED\n\
+       192.0.2.8 dev eth0 lladdr (incomplete) INCOMPLETE\n\
+       198.51.100.2 dev ib0 lladdr {IB} used 10/10/3 probes 1 DELAY\n\
+       \n\
+       192.0.2.20 dev eth0 proxy\n"
+    );
+    let entries = parse_ip_neigh(&text).unwrap();
+    assert_eq!(entries.len(), 6);
+
+    assert_eq!(entries[0].hardware_addr(), mac());
+    assert_eq!(entries[0].state(), Some(NeighborState::Reachable));
+    assert!(entries[0].flags().is_empty());
+
+    assert!(entries[1].ip().is_ipv6());
+    assert_eq!(entries[1].flags(), NeighborFlags::ROUTER);
+    assert_eq!(entries[1].state(), Some(NeighborState::Stale));
+
+    assert_eq!(entries[2].hardware_addr(), None);
+    assert_eq!(entries[2].state(), Some(NeighborState::Failed));
+    assert_eq!(entries[3].hardware_addr(), None);
+    assert_eq!(entries[3].state(), Some(NeighborState::Incomplete));
+
+    assert_eq!(entries[4].hardware_addr(), ib());
+    assert_eq!(entries[4].state(), Some(NeighborState::Delay));
+
+    assert_eq!(entries[5].flags(), NeighborFlags::PROXY);
+    assert_eq!(entries[5].state(), None);
+
+    assert_eq!(entries[0].dev(), Some("eth0"));
+
+    // `ip neigh show dev eth0` leaves out the device.
+    let entries = parse_ip_neigh(
+      "192.0.2.1 lladdr 00:00:5e:00:53:01 REACHABLE
+       192.0.2.7  FAILED
+",
+    )
+    .unwrap();
+    assert_eq!(entries[0].dev(), None);
+    assert_eq!(entries[0].hardware_addr(), mac());
+    assert_eq!(entries[1].state(), Some(NeighborState::Failed));
+
+    assert_eq!(
+      parse_ip_neigh("192.0.2.1 dev"),
+      Err(NeighborParseError::MissingField {
+        line: 1,
+        field: "dev"
+      })
+    );
+    assert_eq!(
+      parse_ip_neigh("192.0.2.1 dev eth0\nnot-an-ip dev eth0"),
+      Err(NeighborParseError::InvalidField {
+        line: 2,
+        field: "address"
+      })
+    );
+  }
+
+  #[cfg(feature = "serde")]
+  #[test]
+  fn ip_json_neigh() {
+    let json = std::format!(
+      r#"[
+        {{"dst":"192.0.2.1","dev":"eth0","lladdr":"00:00:5e:00:53:01","state":["REACHABLE"]}},
+        {{"dst":"fe80::1","dev":"eth0","lladdr":"00:00:5e:00:53:01","router":null,"state":["STALE"]}},
+        {{"dst":"192.0.2.7","dev":"eth0","state":["FAILED"]}},
+        {{"dst":"198.51.100.2","dev":"ib0","lladdr":"{IB}","state":["PERMANENT"]}}
+      ]"#
+    );
+    let entries: Vec<NeighborEntry> = serde_json::from_str(&json).unwrap();
+    let text = std::format!(
+      "192.0.2.1 dev eth0 lladdr 00:00:5e:00:53:01 REACHABLE\n\
+       fe80::1 dev eth0 lladdr 00:00:5e:00:53:01 router STALE\n\
+       192.0.2.7 dev eth0 FAILED\n\
+       198.51.100.2 dev ib0 lladdr {IB} PERMANENT\n"
+    );
+    assert_eq!(entries, parse_ip_neigh(&text).unwrap());
+
+    // `ip -j neigh show dev eth0` leaves out the device.
+    let entries: Vec<NeighborEntry> = serde_json::from_str(
+      r#"[{"dst":"192.0.2.1","lladdr":"00:00:5e:00:53:01","state":["REACHABLE"]}]"#,
+    )
+    .unwrap();
+    assert_eq!(
+      entries,
+      parse_ip_neigh("192.0.2.1 lladdr 00:00:5e:00:53:01 REACHABLE").unwrap()
+    );
+    assert_eq!(entries[0].dev(), None);
+
+    assert!(serde_json::from_str::<NeighborEntry>(
+      r#"{"dst":"192.0.2.1","dev":"eth0","lladdr":"zz"}"#
+    )
+    .is_err());
+  }
+
+  #[test]
+  fn states_and_flags() {
+    for value in [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80] {
+      let state = NeighborState::from_u16(value).unwrap();
+      assert_eq!(state.to_u16(), value);
+      assert_eq!(NeighborState::from_name(state.as_str()), Some(state));
+    }
+    assert_eq!(NeighborState::from_u16(0x03), None);
+
+    let flags = NeighborFlags::ROUTER | NeighborFlags::OFFLOAD;
+    assert_eq!(flags.bits(), 0xa0);
+    assert!(flags.contains(NeighborFlags::ROUTER));
+    assert!(!flags.contains(NeighborFlags::PROXY));
+    assert_eq!(NeighborFlags::from_bits(0xa0), flags);
+  }
+}
@@ -0,0 +1,465 @@
+//! Link-layer address attributes of rtnetlink messages: `IFLA_ADDRESS`,
+//! `IFLA_BROADCAST` and `IFLA_PERM_ADDRESS` of `RTM_NEWLINK`, and
+//! `NDA_LLADDR` of `RTM_NEWNEIGH`.
+//!
+//! Their payloads are raw bytes whose meaning depends on the device type
+//! (`ifi_type`, an [`Arphrd`]); [`LinkAddr`] decodes them into the matching
+//! address type and encodes them back.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hardware_address::{
+//!   netlink::{Arphrd, LinkAddr, IFLA_ADDRESS},
+//!   MacAddr,
+//! };
+//!
+//! let mac = MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]);
+//! let addr = LinkAddr::decode(Arphrd::Ether, mac.as_bytes());
+//! assert_eq!(addr, LinkAddr::Mac(mac));
+//!
+//! let mut buf = [0u8; 12];
+//! assert_eq!(addr.encode_attr(IFLA_ADDRESS, &mut buf), Ok(12));
+//! assert_eq!(LinkAddr::decode_attr(Arphrd::Ether, &buf), Ok((IFLA_ADDRESS, addr)));
+//! ```
+
+use crate::{AnyHardwareAddr, Eui64Addr, InfiniBandAddr, MacAddr};
+
+/// The `IFLA_ADDRESS` attribute type: the device address.
+pub const IFLA_ADDRESS: u16 = 1;
+/// The `IFLA_BROADCAST` attribute type: the device broadcast address.
+pub const IFLA_BROADCAST: u16 = 2;
+/// The `IFLA_PERM_ADDRESS` attribute type: the permanent device address.
+pub const IFLA_PERM_ADDRESS: u16 = 54;
+/// The `NDA_LLADDR` attribute type: the neighbor link-layer address.
+pub const NDA_LLADDR: u16 = 2;
+
+/// The size of an attribute header, `struct rtattr`.
+const RTA_HEADER_LEN: usize = 4;
+
+/// An error returned while decoding or encoding a link-layer address
+/// attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+pub enum NetlinkError {
+  /// The buffer is too short for the attribute.
+  #[error("buffer too short: expected at least {expected} bytes, but got {actual} bytes")]
+  TooShort {
+    /// The number of bytes the attribute needs.
+    expected: usize,
+    /// The length of the buffer.
+    actual: usize,
+  },
+  /// The attribute length is shorter than its header.
+  #[error("invalid attribute length {0}")]
+  InvalidLength(u16),
+  /// The payload does not fit in an attribute, whose length is 16 bits.
+  #[error("attribute payload of {0} bytes is longer than 65531 bytes")]
+  TooLong(usize),
+}
+
+/// Defines [`Arphrd`] and its conversions from one table of variants and
+/// values.
+macro_rules! arphrd {
+  ($($(#[$meta:meta])* $variant:ident = $value:literal),+ $(,)?) => {
+    /// A Linux device type (`ARPHRD_*`), as in `ifi_type` and the sysfs `type`
+    /// attribute.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[non_exhaustive]
+    pub enum Arphrd {
+      $(
+        $(#[$meta])*
+        $variant,
+      )+
+      /// Any other device type.
+      Other(u16),
+    }
+
+    impl Arphrd {
+      /// Returns the device type with value `value`.
+      #[inline]
+      pub const fn from_u16(value: u16) -> Self {
+        match value {
+          $($value => Self::$variant,)+
+          other => Self::Other(other),
+        }
+      }
+
+      /// Returns the value of the device type.
+      #[inline]
+      pub const fn to_u16(self) -> u16 {
+        match self {
+          $(Self::$variant => $value,)+
+          Self::Other(other) => other,
+        }
+      }
+    }
+  };
+}
+
+arphrd! {
+  /// Ethernet, including Wi-Fi, bridges and most virtual devices.
+  Ether = 1,
+  /// IEEE 802.2 (Token Ring).
+  Ieee802 = 6,
+  /// IEEE 1394 (FireWire).
+  Ieee1394 = 24,
+  /// Generic EUI-64 links.
+  Eui64 = 27,
+  /// IP over InfiniBand.
+  Infiniband = 32,
+  /// A PPP link.
+  Ppp = 512,
+  /// An IPIP tunnel.
+  Tunnel = 768,
+  /// An IP6IP6 tunnel.
+  Tunnel6 = 769,
+  /// The loopback device.
+  Loopback = 772,
+  /// An IPv6-in-IPv4 (SIT) tunnel.
+  Sit = 776,
+  /// A GRE tunnel.
+  IpGre = 778,
+  /// IEEE 802.11 with an 802.11 header (monitor mode).
+  Ieee80211 = 801,
+  /// IEEE 802.11 with a radiotap header (monitor mode).
+  Ieee80211Radiotap = 803,
+  /// IEEE 802.15.4.
+  Ieee802154 = 804,
+  /// An IP6GRE tunnel.
+  Ip6Gre = 823,
+  /// A raw IP device, such as WireGuard or TUN.
+  None = 65534,
+}
+
+impl From<u16> for Arphrd {
+  #[inline]
+  fn from(value: u16) -> Self {
+    Self::from_u16(value)
+  }
+}
+
+impl From<Arphrd> for u16 {
+  #[inline]
+  fn from(value: Arphrd) -> Self {
+    value.to_u16()
+  }
+}
+
+/// A link-layer address attribute payload, typed by device type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinkAddr<'a> {
+  /// A MAC address of an Ethernet, IEEE 802.11 or loopback device.
+  Mac(MacAddr),
+  /// An EUI-64 address of an IEEE 1394, IEEE 802.15.4 or EUI-64 device.
+  Eui64(Eui64Addr),
+  /// An IP over InfiniBand address.
+  InfiniBand(InfiniBandAddr),
+  /// The payload of any other device type, or of an unexpected size.
+  Raw(&'a [u8]),
+}
+
+impl<'a> LinkAddr<'a> {
+  /// Decodes the payload of an attribute of a device of type `arphrd`.
+  ///
+  /// Payloads of another size than the device type uses, such as the
+  /// 16-byte addresses of Linux FireWire devices, decode as
+  /// [`Raw`](Self::Raw).
+  pub fn decode(arphrd: Arphrd, payload: &'a [u8]) -> Self {
+    match arphrd {
+      Arphrd::Ether
+      | Arphrd::Ieee802
+      | Arphrd::Ieee80211
+      | Arphrd::Ieee80211Radiotap
+      | Arphrd::Loopback => MacAddr::try_from(payload).map_or(Self::Raw(payload), Self::Mac),
+      Arphrd::Ieee1394 | Arphrd::Eui64 | Arphrd::Ieee802154 => {
+        Eui64Addr::try_from(payload).map_or(Self::Raw(payload), Self::Eui64)
+      }
+      Arphrd::Infiniband => {
+        InfiniBandAddr::try_from(payload).map_or(Self::Raw(payload), Self::InfiniBand)
+      }
+      _ => Self::Raw(payload),
+    }
+  }
+
+  /// Decodes an attribute (`struct rtattr`, in host byte order) at the
+  /// start of `attr`, returning its type and payload.
+  ///
+  /// Fails if the attribute is truncated; the type is not checked.
+  pub fn decode_attr(arphrd: Arphrd, attr: &'a [u8]) -> Result<(u16, Self), NetlinkError> {
+    if attr.len() < RTA_HEADER_LEN {
+      return Err(NetlinkError::TooShort {
+        expected: RTA_HEADER_LEN,
+        actual: attr.len(),
+      });
+    }
+    let len = u16::from_ne_bytes([attr[0], attr[1]]);
+    let ty = u16::from_ne_bytes([attr[2], attr[3]]);
+    if (len as usize) < RTA_HEADER_LEN {
+      return Err(NetlinkError::InvalidLength(len));
+    }
+    if attr.len() < len as usize {
+      return Err(NetlinkError::TooShort {
+        expected: len as usize,
+        actual: attr.len(),
+      });
+    }
+    Ok((
+      ty,
+      Self::decode(arphrd, &attr[RTA_HEADER_LEN..len as usize]),
+    ))
+  }
+
+  /// Returns the payload bytes.
+  #[inline]
+  pub const fn as_bytes(&self) -> &[u8] {
+    match self {
+      Self::Mac(addr) => addr.as_bytes(),
+      Self::Eui64(addr) => addr.as_bytes(),
+      Self::InfiniBand(addr) => addr.as_bytes(),
+      Self::Raw(payload) => payload,
+    }
+  }
+
+  /// Returns the typed address, or `None` for a raw payload.
+  #[inline]
+  pub const fn hardware_addr(&self) -> Option<AnyHardwareAddr> {
+    match self {
+      Self::Mac(addr) => Some(AnyHardwareAddr::Mac(*addr)),
+      Self::Eui64(addr) => Some(AnyHardwareAddr::Eui64(*addr)),
+      Self::InfiniBand(addr) => Some(AnyHardwareAddr::InfiniBand(*addr)),
+      Self::Raw(_) => None,
+    }
+  }
+
+  /// Returns the length of the encoded attribute, including its header and
+  /// the padding to a multiple of 4 bytes.
+  #[inline]
+  pub const fn encoded_attr_len(&self) -> usize {
+    (RTA_HEADER_LEN + self.as_bytes().len() + 3) & !3
+  }
+
+  /// Encodes the payload to the start of `buf`, returning its length.
+  pub fn encode(&self, buf: &mut [u8]) -> Result<usize, NetlinkError> {
+    let payload = self.as_bytes();
+    if buf.len() < payload.len() {
+      return Err(NetlinkError::TooShort {
+        expected: payload.len(),
+        actual: buf.len(),
+      });
+    }
+    buf[..payload.len()].copy_from_slice(payload);
+    Ok(payload.len())
+  }
+
+  /// Encodes an attribute of type `ty` (`struct rtattr`, in host byte
+  /// order) to the start of `buf`, zeroing the padding, and returns its
+  /// padded length.
+  pub fn encode_attr(&self, ty: u16, buf: &mut [u8]) -> Result<usize, NetlinkError> {
+    let payload = self.as_bytes();
+    let len = match u16::try_from(RTA_HEADER_LEN + payload.len()) {
+      Ok(len) => len,
+      Err(_) => return Err(NetlinkError::TooLong(payload.len())),
+    };
+    let padded = self.encoded_attr_len();
+    if buf.len() < padded {
+      return Err(NetlinkError::TooShort {
+        expected: padded,
+        actual: buf.len(),
+      });
+    }
+    buf[..2].copy_from_slice(&len.to_ne_bytes());
+    buf[2..4].copy_from_slice(&ty.to_ne_bytes());
+    buf[RTA_HEADER_LEN..len as usize].copy_from_slice(payload);
+    buf[len as usize..padded].fill(0);
+    Ok(padded)
+  }
+}
+
+impl From<MacAddr> for LinkAddr<'_> {
+  #[inline]
+  fn from(addr: MacAddr) -> Self {
+    Self::Mac(addr)
+  }
+}
+
+impl From<Eui64Addr> for LinkAddr<'_> {
+  #[inline]
+  fn from(addr: Eui64Addr) -> Self {
+    Self::Eui64(addr)
+  }
+}
+
+impl From<InfiniBandAddr> for LinkAddr<'_> {
+  #[inline]
+  fn from(addr: InfiniBandAddr) -> Self {
+    Self::InfiniBand(addr)
+  }
+}
+
+impl From<AnyHardwareAddr> for LinkAddr<'_> {
+  #[inline]
+  fn from(addr: AnyHardwareAddr) -> Self {
+    match addr {
+      AnyHardwareAddr::Mac(addr) => Self::Mac(addr),
+      AnyHardwareAddr::Eui64(addr) => Self::Eui64(addr),
+      AnyHardwareAddr::InfiniBand(addr) => Self::InfiniBand(addr),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // Hand-written attributes laid out as the kernel sends them in
+  // `RTM_NEWLINK` and `RTM_NEWNEIGH` replies on a little-endian host, using
+  // documentation addresses.
+  const ETH0_ADDRESS: [u8; 12] = [
+    0x0a, 0x00, 0x01, 0x00, 0x00, 0x00, 0x5e, 0x00, 0x53, 0x01, 0x00, 0x00,
+  ];
+  const ETH0_BROADCAST: [u8; 12] = [
+    0x0a, 0x00, 0x02, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00,
+  ];
+  const IB0_PERM_ADDRESS: [u8; 24] = [
+    0x18, 0x00, 0x36, 0x00, 0x80, 0x00, 0x02, 0x08, 0xfe, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x02, 0xc9, 0x03, 0x00, 0x0a, 0x0b, 0x0c,
+  ];
+  const WPAN0_ADDRESS: [u8; 12] = [
+    0x0c, 0x00, 0x01, 0x00, 0x02, 0x00, 0x5e, 0x10, 0x00, 0x00, 0x00, 0x01,
+  ];
+  const TUNL0_ADDRESS: [u8; 8] = [0x08, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00];
+
+  #[cfg(target_endian = "little")]
+  #[test]
+  fn kernel_layout() {
+    let cases: [(Arphrd, &[u8], u16, Option<AnyHardwareAddr>); 5] = [
+      (
+        Arphrd::Ether,
+        &ETH0_ADDRESS,
+        IFLA_ADDRESS,
+        Some(MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]).into()),
+      ),
+      (
+        Arphrd::Ether,
+        &ETH0_BROADCAST,
+        IFLA_BROADCAST,
+        Some(MacAddr::from_raw([0xff; 6]).into()),
+      ),
+      (
+        Arphrd::Infiniband,
+        &IB0_PERM_ADDRESS,
+        IFLA_PERM_ADDRESS,
+        Some(
+          InfiniBandAddr::try_from(&IB0_PERM_ADDRESS[4..])
+            .unwrap()
+            .into(),
+        ),
+      ),
+      (
+        Arphrd::Ieee802154,
+        &WPAN0_ADDRESS,
+        IFLA_ADDRESS,
+        Some(Eui64Addr::from_raw([0x02, 0x00, 0x5e, 0x10, 0x00, 0x00, 0x00, 0x01]).into()),
+      ),
+      (Arphrd::Tunnel, &TUNL0_ADDRESS, IFLA_ADDRESS, None),
+    ];
+    for (arphrd, attr, ty, addr) in cases {
+      let (decoded_ty, decoded) = LinkAddr::decode_attr(arphrd, attr).unwrap();
+      assert_eq!(decoded_ty, ty);
+      assert_eq!(decoded.hardware_addr(), addr);
+      assert_eq!(
+        decoded.as_bytes(),
+        &attr[4..u16::from_ne_bytes([attr[0], attr[1]]) as usize]
+      );
+
+      let mut buf = [0xffu8; 32];
+      assert_eq!(decoded.encode_attr(ty, &mut buf), Ok(attr.len()));
+      assert_eq!(&buf[..attr.len()], attr);
+      assert_eq!(buf[attr.len()], 0xff);
+    }
+  }
+
+  #[test]
+  fn decode_by_type() {
+    let bytes = [0x11u8; 20];
+    assert_eq!(
+      LinkAddr::decode(Arphrd::Loopback, &[0; 6]),
+      LinkAddr::Mac(MacAddr::new())
+    );
+    assert_eq!(
+      LinkAddr::decode(Arphrd::Ieee1394, &bytes[..8]),
+      LinkAddr::Eui64(Eui64Addr::from_raw([0x11; 8]))
+    );
+    // Linux FireWire devices use 16-byte addresses.
+    assert_eq!(
+      LinkAddr::decode(Arphrd::Ieee1394, &bytes[..16]),
+      LinkAddr::Raw(&bytes[..16])
+    );
+    assert_eq!(
+      LinkAddr::decode(Arphrd::Ether, &bytes[..8]),
+      LinkAddr::Raw(&bytes[..8])
+    );
+    assert_eq!(
+      LinkAddr::decode(Arphrd::None, &bytes),
+      LinkAddr::Raw(&bytes)
+    );
+    assert_eq!(
+      LinkAddr::decode(Arphrd::Infiniband, &bytes).hardware_addr(),
+      Some(InfiniBandAddr::from_raw(bytes).into())
+    );
+
+    let addr = LinkAddr::from(AnyHardwareAddr::Mac(MacAddr::from_raw([1; 6])));
+    let mut buf = [0u8; 6];
+    assert_eq!(addr.encode(&mut buf), Ok(6));
+    assert_eq!(buf, [1; 6]);
+  }
+
+  #[test]
+  fn malformed() {
+    assert_eq!(
+      LinkAddr::decode_attr(Arphrd::Ether, &[0x0a, 0x00]),
+      Err(NetlinkError::TooShort {
+        expected: 4,
+        actual: 2
+      })
+    );
+    assert_eq!(
+      LinkAddr::decode_attr(Arphrd::Ether, &0u32.to_ne_bytes()),
+      Err(NetlinkError::InvalidLength(0))
+    );
+    assert_eq!(
+      LinkAddr::decode_attr(Arphrd::Ether, &ETH0_ADDRESS[..8]),
+      Err(NetlinkError::TooShort {
+        expected: u16::from_ne_bytes([0x0a, 0x00]) as usize,
+        actual: 8
+      })
+    );
+    let addr = LinkAddr::Mac(MacAddr::new());
+    assert_eq!(
+      addr.encode_attr(IFLA_ADDRESS, &mut [0; 10]),
+      Err(NetlinkError::TooShort {
+        expected: 12,
+        actual: 10
+      })
+    );
+    assert_eq!(
+      addr.encode(&mut [0; 5]),
+      Err(NetlinkError::TooShort {
+        expected: 6,
+        actual: 5
+      })
+    );
+
+    let payload = [0u8; u16::MAX as usize - RTA_HEADER_LEN + 1];
+    assert_eq!(
+      LinkAddr::Raw(&payload).encode_attr(IFLA_ADDRESS, &mut [0; 12]),
+      Err(NetlinkError::TooLong(payload.len()))
+    );
+    let payload = &payload[1..];
+    let mut buf = [0u8; u16::MAX as usize + 1];
+    assert_eq!(
+      LinkAddr::Raw(payload).encode_attr(IFLA_ADDRESS, &mut buf),
+      Ok(u16::MAX as usize + 1)
+    );
+  }
+}
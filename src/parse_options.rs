@@ -0,0 +1,185 @@
+use core::fmt;
+
+use crate::Case;
+
+/// A textual notation of a hardware address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Notation {
+  /// Colon-separated octets, e.g. `00:00:5e:00:53:01`.
+  Colon,
+  /// Hyphen-separated octets, e.g. `00-00-5e-00-53-01`.
+  Hyphen,
+  /// Dot-separated groups of two octets, e.g. `0000.5e00.5301`.
+  Dot,
+  /// Unseparated hex digits, optionally `0x`-prefixed, e.g. `00005e005301`.
+  BareHex,
+}
+
+impl Notation {
+  #[inline]
+  const fn bit(&self) -> u8 {
+    match self {
+      Self::Colon => 1 << 0,
+      Self::Hyphen => 1 << 1,
+      Self::Dot => 1 << 2,
+      Self::BareHex => 1 << 3,
+    }
+  }
+
+  /// Returns a short human-readable name, e.g. `"colon-separated"`.
+  #[inline]
+  pub const fn as_str(&self) -> &'static str {
+    match self {
+      Self::Colon => "colon-separated",
+      Self::Hyphen => "hyphen-separated",
+      Self::Dot => "dot-separated",
+      Self::BareHex => "bare hex",
+    }
+  }
+}
+
+impl fmt::Display for Notation {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.pad(self.as_str())
+  }
+}
+
+/// Restricts which inputs `parse_with` accepts: the allowed notations and
+/// the required case of the hex digits.
+///
+/// The default accepts everything [`parse`](crate::parse) accepts. Use it to
+/// enforce a canonical input form, e.g. in configuration validation.
+///
+/// ## Example
+///
+/// ```rust
+/// use hardware_address::{Case, MacAddr, Notation, ParseErrorKind, ParseOptions};
+///
+/// const CANONICAL: ParseOptions = ParseOptions::COLON.with_case(Some(Case::Lower));
+///
+/// assert!(MacAddr::parse_with("00:00:5e:00:53:01", CANONICAL).is_ok());
+/// assert_eq!(
+///   MacAddr::parse_with("00-00-5e-00-53-01", CANONICAL).unwrap_err().kind(),
+///   ParseErrorKind::DisallowedNotation(Notation::Hyphen),
+/// );
+/// assert!(MacAddr::parse_with("00:00:5E:00:53:01", CANONICAL).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseOptions {
+  notations: u8,
+  case: Option<Case>,
+}
+
+impl Default for ParseOptions {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl ParseOptions {
+  /// Only the colon-separated notation.
+  pub const COLON: Self = Self::none().with_notation(Notation::Colon, true);
+
+  /// Only the hyphen-separated notation.
+  pub const HYPHEN: Self = Self::none().with_notation(Notation::Hyphen, true);
+
+  /// Only the dot-separated notation.
+  pub const DOT: Self = Self::none().with_notation(Notation::Dot, true);
+
+  /// Only bare hex, with or without a `0x` prefix.
+  pub const BARE_HEX: Self = Self::none().with_notation(Notation::BareHex, true);
+
+  /// Creates the default options: every notation is allowed and hex digits
+  /// may be of any case.
+  #[inline]
+  pub const fn new() -> Self {
+    Self {
+      notations: Notation::Colon.bit()
+        | Notation::Hyphen.bit()
+        | Notation::Dot.bit()
+        | Notation::BareHex.bit(),
+      case: None,
+    }
+  }
+
+  #[inline]
+  const fn none() -> Self {
+    Self {
+      notations: 0,
+      case: None,
+    }
+  }
+
+  /// Sets whether `notation` is accepted.
+  ///
+  /// Every notation is accepted by default.
+  #[inline]
+  pub const fn with_notation(mut self, notation: Notation, allow: bool) -> Self {
+    if allow {
+      self.notations |= notation.bit();
+    } else {
+      self.notations &= !notation.bit();
+    }
+    self
+  }
+
+  /// Sets whether bare hex (`00005e005301` or `0x00005e005301`) is
+  /// accepted. Shorthand for `with_notation(Notation::BareHex, allow)`.
+  #[inline]
+  pub const fn with_bare_hex(self, allow: bool) -> Self {
+    self.with_notation(Notation::BareHex, allow)
+  }
+
+  /// Sets the case every hex digit must have, or `None` to accept any case.
+  ///
+  /// Only the hex digits are checked, not a `0x` prefix.
+  ///
+  /// Default is `None`.
+  #[inline]
+  pub const fn with_case(mut self, case: Option<Case>) -> Self {
+    self.case = case;
+    self
+  }
+
+  /// Returns whether `notation` is accepted.
+  #[inline]
+  pub const fn allows(&self, notation: Notation) -> bool {
+    self.notations & notation.bit() != 0
+  }
+
+  /// Returns the case every hex digit must have, if any.
+  #[inline]
+  pub const fn case(&self) -> Option<Case> {
+    self.case
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn builders() {
+    let all = ParseOptions::default();
+    assert_eq!(all, ParseOptions::new());
+    for n in [
+      Notation::Colon,
+      Notation::Hyphen,
+      Notation::Dot,
+      Notation::BareHex,
+    ] {
+      assert!(all.allows(n));
+      assert_eq!(ParseOptions::COLON.allows(n), n == Notation::Colon);
+      assert!(!all.with_notation(n, false).allows(n));
+    }
+    assert!(!all.with_bare_hex(false).allows(Notation::BareHex));
+    assert!(ParseOptions::DOT
+      .with_bare_hex(true)
+      .allows(Notation::BareHex));
+    assert_eq!(all.case(), None);
+    assert_eq!(all.with_case(Some(Case::Upper)).case(), Some(Case::Upper));
+    assert_eq!(std::format!("{}", Notation::BareHex), "bare hex");
+  }
+}
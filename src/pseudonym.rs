@@ -0,0 +1,181 @@
+use crate::siphash::SipHasher24;
+
+/// Domain separation tag for [`Pseudonymizer`]. Changing it changes every
+/// pseudonym, so it is versioned and must stay fixed.
+const PSEUDONYM_DOMAIN: &[u8] = b"hardware-address/pseudonym/v1";
+
+/// Maps hardware addresses to stable pseudonyms under a secret key.
+///
+/// The same key always maps the same address to the same pseudonym, so
+/// datasets pseudonymized with one key can still be joined on the address
+/// column, while the original address cannot be recovered without brute
+/// forcing the key. The pseudonym is derived with SipHash-2-4 over the full
+/// original address, so two addresses sharing an OUI still get unrelated
+/// pseudonyms.
+///
+/// By default the I/G and U/L bits of the original address are kept, so the
+/// pseudonym is an address of the same class (unicast/multicast,
+/// universal/local). [`with_keep_oui`](Self::with_keep_oui) additionally keeps
+/// the first three octets so vendor breakdowns still work.
+///
+/// See [`MacAddr::pseudonymize`](crate::MacAddr::pseudonymize) and
+/// [`Eui64Addr::pseudonymize`](crate::Eui64Addr::pseudonymize).
+///
+/// ## Example
+///
+/// ```rust
+/// use hardware_address::{MacAddr, Pseudonymizer};
+///
+/// let p = Pseudonymizer::new(*b"telemetry-key-01").with_keep_oui(true);
+///
+/// let addr: MacAddr = "00:1b:21:3a:4c:5d".parse().unwrap();
+/// let pseudonym = addr.pseudonymize(&p);
+/// assert_eq!(&pseudonym.octets()[..3], &addr.octets()[..3]);
+/// assert_eq!(pseudonym, addr.pseudonymize(&p));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Pseudonymizer {
+  key: [u8; 16],
+  keep_oui: bool,
+  keep_flags: bool,
+}
+
+impl core::fmt::Debug for Pseudonymizer {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    // Never print the key.
+    f.debug_struct("Pseudonymizer")
+      .field("keep_oui", &self.keep_oui)
+      .field("keep_flags", &self.keep_flags)
+      .finish_non_exhaustive()
+  }
+}
+
+impl Pseudonymizer {
+  /// Creates a pseudonymizer keyed with the secret `key`.
+  ///
+  /// The OUI is not kept, the I/G and U/L bits are.
+  #[inline]
+  pub const fn new(key: [u8; 16]) -> Self {
+    Self {
+      key,
+      keep_oui: false,
+      keep_flags: true,
+    }
+  }
+
+  /// Sets whether the first three octets (the OUI) are copied from the
+  /// original address. Keeping the OUI implies keeping the I/G and U/L bits.
+  ///
+  /// Default is `false`.
+  #[inline]
+  pub const fn with_keep_oui(mut self, keep: bool) -> Self {
+    self.keep_oui = keep;
+    self
+  }
+
+  /// Sets whether the I/G and U/L bits are copied from the original address.
+  ///
+  /// Default is `true`.
+  #[inline]
+  pub const fn with_keep_flags(mut self, keep: bool) -> Self {
+    self.keep_flags = keep;
+    self
+  }
+
+  /// Returns whether the OUI is kept.
+  #[inline]
+  pub const fn keep_oui(&self) -> bool {
+    self.keep_oui
+  }
+
+  /// Returns whether the I/G and U/L bits are kept.
+  #[inline]
+  pub const fn keep_flags(&self) -> bool {
+    self.keep_flags
+  }
+
+  /// Pseudonymizes the `N` octets of an address.
+  ///
+  /// The output is `SipHash-2-4(key, domain || le64(N) || octets || le64(block))`
+  /// for `block = 0, 1, ..` concatenated as little-endian words, then
+  /// truncated to `N` bytes before the OUI and flag bits are restored.
+  pub(crate) const fn pseudonymize<const N: usize>(&self, octets: &[u8; N]) -> [u8; N] {
+    let base = SipHasher24::new(&self.key)
+      .write(PSEUDONYM_DOMAIN)
+      .write_u64(N as u64)
+      .write(octets);
+
+    let mut out = [0u8; N];
+    let mut block = 0usize;
+    while block * 8 < N {
+      let h = base.write_u64(block as u64).finish().to_le_bytes();
+      let mut i = 0;
+      while i < 8 && block * 8 + i < N {
+        out[block * 8 + i] = h[i];
+        i += 1;
+      }
+      block += 1;
+    }
+
+    if self.keep_oui {
+      let mut i = 0;
+      while i < 3 && i < N {
+        out[i] = octets[i];
+        i += 1;
+      }
+    } else if self.keep_flags && N > 0 {
+      const FLAGS: u8 = crate::__private::IG_BIT | crate::__private::UL_BIT;
+      out[0] = (out[0] & !FLAGS) | (octets[0] & FLAGS);
+    }
+
+    out
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const KEY: [u8; 16] = *b"0123456789abcdef";
+
+  #[test]
+  fn deterministic_and_keyed() {
+    let p = Pseudonymizer::new(KEY);
+    let addr = [0x00, 0x1b, 0x21, 0x3a, 0x4c, 0x5d];
+    assert_eq!(p.pseudonymize(&addr), p.pseudonymize(&addr));
+    assert_ne!(p.pseudonymize(&addr), addr);
+
+    let other = Pseudonymizer::new(*b"fedcba9876543210");
+    assert_ne!(p.pseudonymize(&addr), other.pseudonymize(&addr));
+  }
+
+  #[test]
+  fn keeps_flags_and_oui() {
+    let keep_flags = Pseudonymizer::new(KEY);
+    let keep_oui = Pseudonymizer::new(KEY).with_keep_oui(true);
+    let keep_nothing = Pseudonymizer::new(KEY).with_keep_flags(false);
+    assert!(keep_oui.keep_oui() && keep_oui.keep_flags());
+    assert!(!keep_nothing.keep_flags());
+
+    let mut saw_flag_change = false;
+    for first in 0..=255u8 {
+      let addr = [first, 0x1b, 0x21, 0x3a, 0x4c, 0x5d, 0x6e, 0x7f];
+      let out = keep_flags.pseudonymize(&addr);
+      assert_eq!(out[0] & 0x03, first & 0x03);
+
+      let out = keep_oui.pseudonymize(&addr);
+      assert_eq!(out[..3], addr[..3]);
+      assert_ne!(out[3..], addr[3..]);
+
+      saw_flag_change |= keep_nothing.pseudonymize(&addr)[0] & 0x03 != first & 0x03;
+    }
+    assert!(saw_flag_change);
+  }
+
+  #[test]
+  fn debug_hides_key() {
+    let p = Pseudonymizer::new(KEY);
+    let s = std::format!("{p:?}");
+    assert!(!s.contains("key"), "{s}");
+  }
+}
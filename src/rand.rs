@@ -0,0 +1,130 @@
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __addr_ty_rand {
+  (
+    $name:ident[$n:expr]
+  ) => {
+    const _: () = {
+      impl $name {
+        /// Generates a uniformly random address.
+        ///
+        /// No constraint is applied to the first octet. `MacAddr` and
+        /// `Eui64Addr` also provide `random_local_unicast` and friends when the
+        /// I/G and U/L bits matter.
+        #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+        #[inline]
+        pub fn random<R>(rng: &mut R) -> Self
+        where
+          R: $crate::__private::rand::Rng + ?::core::marker::Sized,
+        {
+          let mut bytes = [0u8; $n];
+          $crate::__private::rand::RngCore::fill_bytes(rng, &mut bytes);
+          $name(bytes)
+        }
+
+        /// Generates a random address starting with `prefix` (e.g. an OUI).
+        ///
+        /// The leading octets are copied verbatim from `prefix`, so the
+        /// I/G and U/L bits are those of the prefix whenever it is not empty.
+        ///
+        /// ## Panics
+        ///
+        /// Panics if `prefix` is longer than the address.
+        #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+        pub fn random_with_prefix<R>(rng: &mut R, prefix: &[::core::primitive::u8]) -> Self
+        where
+          R: $crate::__private::rand::Rng + ?::core::marker::Sized,
+        {
+          ::core::assert!(
+            prefix.len() <= $n,
+            "prefix of {} bytes does not fit in a {}-byte address",
+            prefix.len(),
+            $n,
+          );
+
+          let mut addr = Self::random(rng);
+          addr.0[..prefix.len()].copy_from_slice(prefix);
+          addr
+        }
+      }
+
+      impl $crate::__private::rand::distributions::Distribution<$name>
+        for $crate::__private::rand::distributions::Standard
+      {
+        #[inline]
+        fn sample<R>(&self, rng: &mut R) -> $name
+        where
+          R: $crate::__private::rand::Rng + ?::core::marker::Sized,
+        {
+          $name::random(rng)
+        }
+      }
+    };
+  };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __addr_ty_rand_ieee802 {
+  (
+    $name:ident
+  ) => {
+    const _: () = {
+      impl $name {
+        /// Generates a random unicast address (I/G bit cleared).
+        #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+        #[inline]
+        pub fn random_unicast<R>(rng: &mut R) -> Self
+        where
+          R: $crate::__private::rand::Rng + ?::core::marker::Sized,
+        {
+          let mut addr = Self::random(rng);
+          addr.0[0] &= !$crate::__private::IG_BIT;
+          addr
+        }
+
+        /// Generates a random multicast address (I/G bit set).
+        #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+        #[inline]
+        pub fn random_multicast<R>(rng: &mut R) -> Self
+        where
+          R: $crate::__private::rand::Rng + ?::core::marker::Sized,
+        {
+          let mut addr = Self::random(rng);
+          addr.0[0] |= $crate::__private::IG_BIT;
+          addr
+        }
+
+        /// Generates a random locally administered unicast address
+        /// (I/G bit cleared, U/L bit set).
+        ///
+        /// This is the address class to use for virtual interfaces, test
+        /// fixtures and anything else that must never collide with a
+        /// vendor-assigned address.
+        #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+        #[inline]
+        pub fn random_local_unicast<R>(rng: &mut R) -> Self
+        where
+          R: $crate::__private::rand::Rng + ?::core::marker::Sized,
+        {
+          let mut addr = Self::random(rng);
+          addr.0[0] = (addr.0[0] & !$crate::__private::IG_BIT) | $crate::__private::UL_BIT;
+          addr
+        }
+
+        /// Generates a random locally administered multicast address
+        /// (I/G bit set, U/L bit set).
+        #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+        #[inline]
+        pub fn random_local_multicast<R>(rng: &mut R) -> Self
+        where
+          R: $crate::__private::rand::Rng + ?::core::marker::Sized,
+        {
+          let mut addr = Self::random(rng);
+          addr.0[0] |= $crate::__private::IG_BIT | $crate::__private::UL_BIT;
+          addr
+        }
+      }
+    };
+  };
+}
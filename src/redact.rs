@@ -0,0 +1,201 @@
+use core::fmt::{self, Write};
+
+/// A [`Display`](core::fmt::Display) adapter that prints an address with its
+/// trailing octets masked, e.g. `00:11:22:xx:xx:xx`.
+///
+/// Created by the `redacted` method of the address types, for example
+/// [`MacAddr::redacted`](crate::MacAddr::redacted). `L` is the length of the
+/// colon-separated form of the address the adapter was built from; the
+/// adapter only keeps that (stack allocated) buffer and never allocates.
+///
+/// By default the first three octets (the OUI) are visible, masked digits are
+/// printed as `x` and octets are separated by `:`. Its [`Debug`] output is the
+/// same as its [`Display`](core::fmt::Display) output, so the hidden octets
+/// never leak through `{:?}` either. Width, fill and alignment are honored,
+/// e.g. `{:>20}`.
+///
+/// ## Example
+///
+/// ```rust
+/// use hardware_address::MacAddr;
+///
+/// let addr: MacAddr = "00:11:22:33:44:55".parse().unwrap();
+/// assert_eq!(addr.redacted().to_string(), "00:11:22:xx:xx:xx");
+/// assert_eq!(
+///   addr.redacted().with_visible(1).with_mask('*').with_separator('-').to_string(),
+///   "00-**-**-**-**-**",
+/// );
+/// ```
+#[derive(Clone, Copy)]
+pub struct Redacted<const L: usize> {
+  buf: [u8; L],
+  visible: usize,
+  mask: char,
+  separator: char,
+}
+
+impl<const L: usize> Redacted<L> {
+  // Only called by the `redacted` method that `addr_ty!` generates, which
+  // passes the output of `to_colon_separated_array`. Public so that the
+  // macro also works in downstream crates.
+  #[doc(hidden)]
+  #[inline]
+  pub const fn new(colon_separated: [u8; L]) -> Self {
+    Self {
+      buf: colon_separated,
+      visible: 3,
+      mask: 'x',
+      separator: ':',
+    }
+  }
+
+  /// Sets how many leading octets are printed in clear.
+  ///
+  /// Default is `3`.
+  #[inline]
+  pub const fn with_visible(mut self, octets: usize) -> Self {
+    self.visible = octets;
+    self
+  }
+
+  /// Sets the character printed in place of each hidden hex digit.
+  ///
+  /// Default is `'x'`.
+  #[inline]
+  pub const fn with_mask(mut self, mask: char) -> Self {
+    self.mask = mask;
+    self
+  }
+
+  /// Sets the character printed between octets.
+  ///
+  /// Default is `':'`.
+  #[inline]
+  pub const fn with_separator(mut self, separator: char) -> Self {
+    self.separator = separator;
+    self
+  }
+
+  /// Returns how many leading octets are printed in clear.
+  #[inline]
+  pub const fn visible(&self) -> usize {
+    self.visible
+  }
+
+  /// Returns the character printed in place of each hidden hex digit.
+  #[inline]
+  pub const fn mask(&self) -> char {
+    self.mask
+  }
+
+  /// Returns the character printed between octets.
+  #[inline]
+  pub const fn separator(&self) -> char {
+    self.separator
+  }
+}
+
+impl<const L: usize> fmt::Display for Redacted<L> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    // The mask and separator may be multi-byte characters, so the output
+    // cannot be staged in a `[u8; L]` for `Formatter::pad`; pad by hand with
+    // the same rules instead, counting one character per position.
+    let pad = f.width().map_or(0, |width| width.saturating_sub(L));
+    let (pre, post) = match f.align() {
+      Some(fmt::Alignment::Right) => (pad, 0),
+      Some(fmt::Alignment::Center) => (pad / 2, (pad + 1) / 2),
+      _ => (0, pad),
+    };
+    let fill = f.fill();
+    for _ in 0..pre {
+      f.write_char(fill)?;
+    }
+
+    // Every octet takes three positions: two digits and a separator
+    // (except for the last one).
+    for (i, &b) in self.buf.iter().enumerate() {
+      let ch = if i % 3 == 2 {
+        self.separator
+      } else if i / 3 < self.visible {
+        b as char
+      } else {
+        self.mask
+      };
+      f.write_char(ch)?;
+    }
+
+    for _ in 0..post {
+      f.write_char(fill)?;
+    }
+    Ok(())
+  }
+}
+
+impl<const L: usize> fmt::Debug for Redacted<L> {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Display::fmt(self, f)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{Eui64Addr, InfiniBandAddr, MacAddr};
+
+  use std::{format, string::ToString};
+
+  #[test]
+  fn default() {
+    let addr = MacAddr::from_raw([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+    let redacted = addr.redacted();
+    assert_eq!(redacted.to_string(), "00:11:22:xx:xx:xx");
+    assert_eq!(format!("{redacted:?}"), "00:11:22:xx:xx:xx");
+    assert_eq!(redacted.visible(), 3);
+    assert_eq!(redacted.mask(), 'x');
+    assert_eq!(redacted.separator(), ':');
+  }
+
+  #[test]
+  fn configured() {
+    let addr = MacAddr::from_raw([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+    assert_eq!(
+      addr.redacted().with_visible(0).to_string(),
+      "xx:xx:xx:xx:xx:xx"
+    );
+    assert_eq!(
+      addr.redacted().with_visible(usize::MAX).to_string(),
+      "00:11:22:33:44:55"
+    );
+    assert_eq!(
+      addr
+        .redacted()
+        .with_visible(5)
+        .with_mask('•')
+        .with_separator('.')
+        .to_string(),
+      "00.11.22.33.44.••"
+    );
+
+    let addr = Eui64Addr::from_raw([0x02, 0x00, 0x5e, 0x10, 0x00, 0x00, 0x00, 0x01]);
+    assert_eq!(addr.redacted().to_string(), "02:00:5e:xx:xx:xx:xx:xx");
+
+    let addr = InfiniBandAddr::from_raw([0xab; 20]);
+    assert_eq!(
+      addr.redacted().with_visible(4).with_mask('_').to_string(),
+      "ab:ab:ab:ab:__:__:__:__:__:__:__:__:__:__:__:__:__:__:__:__"
+    );
+  }
+
+  #[test]
+  fn padded() {
+    let redacted = MacAddr::from_raw([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]).redacted();
+    assert_eq!(format!("{redacted:20}"), "00:11:22:xx:xx:xx   ");
+    assert_eq!(format!("{redacted:>20}"), "   00:11:22:xx:xx:xx");
+    assert_eq!(format!("{redacted:*^20}"), "*00:11:22:xx:xx:xx**");
+    assert_eq!(format!("{redacted:5}"), "00:11:22:xx:xx:xx");
+    assert_eq!(
+      format!("{:>19?}", redacted.with_mask('•')),
+      "  00:11:22:••:••:••"
+    );
+  }
+}
@@ -0,0 +1,199 @@
+use core::{iter::FusedIterator, ops::Range};
+
+use crate::{bare_hex_format_len, colon_separated_format_len, dot_separated_format_len, parse};
+
+/// An iterator over the `N`-octet addresses found in a text.
+///
+/// Yields the byte range of every match together with the parsed octets,
+/// without allocating. Every notation [`parse`] accepts is recognized:
+/// colon-, hyphen- and dot-separated, and bare hex with an optional `0x`
+/// prefix. Matches never overlap.
+///
+/// A match must stand on its own: it is skipped if the byte before it is
+/// alphanumeric or one of `:`, `-`, `.`, `_`, or if the byte after it is
+/// alphanumeric or such a separator followed by an alphanumeric byte. This
+/// keeps parts of longer tokens from being reported, such as the tail of
+/// an IPv6 address, six of the eight octets of an EUI-64 or twelve digits of
+/// a longer hex string, while trailing punctuation like `.` or `,` is fine.
+///
+/// Created by [`find_iter`] or the `find_iter` method of the address types.
+///
+/// ## Example
+///
+/// ```rust
+/// use hardware_address::find_iter;
+///
+/// let line = "eth0: link up, lladdr 00:00:5e:00:53:01 (was 0000.5e00.5302).";
+/// let found: Vec<_> = find_iter::<6>(line).collect();
+/// assert_eq!(found.len(), 2);
+/// assert_eq!(&line[found[0].0.clone()], "00:00:5e:00:53:01");
+/// assert_eq!(found[1].1, [0x00, 0x00, 0x5e, 0x00, 0x53, 0x02]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AddrScanner<'a, const N: usize> {
+  src: &'a [u8],
+  pos: usize,
+}
+
+impl<'a, const N: usize> AddrScanner<'a, N> {
+  /// Creates a scanner over `src`.
+  #[inline]
+  pub const fn new(src: &'a str) -> Self {
+    Self {
+      src: src.as_bytes(),
+      pos: 0,
+    }
+  }
+
+  /// Returns the parsed address at `start`, if one of the notations matches
+  /// there and ends on a word boundary.
+  fn match_at(&self, start: usize) -> Option<(usize, [u8; N])> {
+    // Some notations share a length (e.g. the dot-separated and the
+    // `0x`-prefixed form of a 6-octet address), `parse` tells those apart,
+    // so every length is only tried once.
+    let mut lens = [
+      colon_separated_format_len::<N>(),
+      dot_separated_format_len::<N>(),
+      bare_hex_format_len::<N>() + 2,
+      bare_hex_format_len::<N>(),
+    ];
+    lens.sort_unstable_by(|a, b| b.cmp(a));
+
+    for (i, &len) in lens.iter().enumerate() {
+      if i > 0 && lens[i - 1] == len {
+        continue;
+      }
+      let end = start + len;
+      if end > self.src.len() || !self.is_end_boundary(end) {
+        continue;
+      }
+      if let Ok(octets) = parse::<N>(&self.src[start..end]) {
+        return Some((end, octets));
+      }
+    }
+    None
+  }
+
+  #[inline]
+  fn is_start_boundary(&self, start: usize) -> bool {
+    start == 0 || !is_word_byte(self.src[start - 1])
+  }
+
+  #[inline]
+  fn is_end_boundary(&self, end: usize) -> bool {
+    match self.src.get(end) {
+      None => true,
+      Some(b) if b.is_ascii_alphanumeric() => false,
+      Some(&b) if is_separator(b) => {
+        !matches!(self.src.get(end + 1), Some(n) if n.is_ascii_alphanumeric())
+      }
+      Some(_) => true,
+    }
+  }
+}
+
+impl<const N: usize> Iterator for AddrScanner<'_, N> {
+  type Item = (Range<usize>, [u8; N]);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    while self.pos < self.src.len() {
+      let start = self.pos;
+      if self.src[start].is_ascii_hexdigit() && self.is_start_boundary(start) {
+        if let Some((end, octets)) = self.match_at(start) {
+          self.pos = end;
+          return Some((start..end, octets));
+        }
+      }
+      self.pos += 1;
+    }
+    None
+  }
+}
+
+impl<const N: usize> FusedIterator for AddrScanner<'_, N> {}
+
+/// Returns an iterator over the `N`-octet addresses found in `src`, with their
+/// byte ranges.
+///
+/// See [`AddrScanner`] for the recognized notations and word boundary rules.
+#[inline]
+pub const fn find_iter<const N: usize>(src: &str) -> AddrScanner<'_, N> {
+  AddrScanner::new(src)
+}
+
+#[inline]
+const fn is_separator(b: u8) -> bool {
+  matches!(b, b':' | b'-' | b'.' | b'_')
+}
+
+#[inline]
+const fn is_word_byte(b: u8) -> bool {
+  b.is_ascii_alphanumeric() || is_separator(b)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{Eui64Addr, MacAddr};
+
+  use std::vec::Vec;
+
+  fn spans<const N: usize>(src: &str) -> Vec<&str> {
+    find_iter::<N>(src).map(|(r, _)| &src[r]).collect()
+  }
+
+  #[test]
+  fn notations() {
+    let text = "a 00:00:5e:00:53:01, b 00-00-5E-00-53-02; c 0000.5e00.5303\n\
+                d 00005e005304 e 0x00005e005305 (f=00:00:5e:00:53:06).";
+    let found: Vec<_> = find_iter::<6>(text).collect();
+    assert_eq!(found.len(), 6);
+    for (i, (range, octets)) in found.iter().enumerate() {
+      assert_eq!(octets, &[0x00, 0x00, 0x5e, 0x00, 0x53, i as u8 + 1]);
+      assert_eq!(parse::<6>(text[range.clone()].as_bytes()), Ok(*octets));
+    }
+    assert_eq!(&text[found[4].0.clone()], "0x00005e005305");
+  }
+
+  #[test]
+  fn show_mac_address_table() {
+    let table = "\
+Vlan    Mac Address       Type        Ports
+----    -----------       --------    -----
+   1    0011.2233.4455    DYNAMIC     Gi0/1
+  20    aabb.ccdd.eeff    STATIC      Gi0/2
+";
+    assert_eq!(spans::<6>(table), ["0011.2233.4455", "aabb.ccdd.eeff"]);
+  }
+
+  #[test]
+  fn word_boundaries() {
+    // Tail of an IPv6 address.
+    assert!(spans::<6>("fe80::aa:bb:cc:dd:ee:ff").is_empty());
+    // Part of an EUI-64.
+    assert!(spans::<6>("02:00:5e:10:00:00:00:01").is_empty());
+    assert_eq!(
+      spans::<8>("eui 02:00:5e:10:00:00:00:01."),
+      ["02:00:5e:10:00:00:00:01"]
+    );
+    // Part of a longer hex string or word.
+    assert!(spans::<6>("sha 0123456789abcdef0123").is_empty());
+    assert!(spans::<6>("x00:00:5e:00:53:01").is_empty());
+    assert!(spans::<6>("00:00:5e:00:53:01x").is_empty());
+    assert!(spans::<6>("00:00:5e:00:53:01.a").is_empty());
+    // Non-ASCII neighbours are boundaries.
+    assert_eq!(spans::<6>("→00:00:5e:00:53:01←"), ["00:00:5e:00:53:01"]);
+    // Mixed separators are not an address.
+    assert!(spans::<6>("00:00-5e:00:53:01").is_empty());
+    assert!(spans::<6>("").is_empty());
+  }
+
+  #[test]
+  fn typed() {
+    let text = "src=00:00:5e:00:53:01 dst=ff:ff:ff:ff:ff:ff";
+    let addrs: Vec<_> = MacAddr::find_iter(text).map(|(_, a)| a).collect();
+    assert_eq!(addrs.len(), 2);
+    assert!(addrs[1].is_multicast());
+    assert_eq!(Eui64Addr::find_iter(text).count(), 0);
+  }
+}
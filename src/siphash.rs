@@ -0,0 +1,154 @@
+//! A portable, `const`-friendly SipHash-2-4 implementation.
+//!
+//! `core::hash::SipHasher` is deprecated and its output is explicitly not
+//! guaranteed to be stable, so anything that derives persistent values from a
+//! keyed hash goes through this implementation instead.
+
+/// SipHash-2-4 keyed with a 128-bit key, producing a 64-bit output.
+///
+/// The state is threaded by value so every operation can be `const`.
+#[derive(Clone, Copy)]
+pub(crate) struct SipHasher24 {
+  v0: u64,
+  v1: u64,
+  v2: u64,
+  v3: u64,
+  /// Pending bytes that do not yet form a full 8-byte word.
+  tail: u64,
+  /// Number of valid bytes in `tail`.
+  ntail: usize,
+  /// Total number of bytes written so far.
+  len: usize,
+}
+
+impl SipHasher24 {
+  /// Creates a hasher keyed with the 16-byte `key` (read as two
+  /// little-endian `u64` halves, as in the reference implementation).
+  #[inline]
+  pub(crate) const fn new(key: &[u8; 16]) -> Self {
+    let k0 = u64::from_le_bytes([
+      key[0], key[1], key[2], key[3], key[4], key[5], key[6], key[7],
+    ]);
+    let k1 = u64::from_le_bytes([
+      key[8], key[9], key[10], key[11], key[12], key[13], key[14], key[15],
+    ]);
+
+    Self {
+      v0: k0 ^ 0x736f_6d65_7073_6575,
+      v1: k1 ^ 0x646f_7261_6e64_6f6d,
+      v2: k0 ^ 0x6c79_6765_6e65_7261,
+      v3: k1 ^ 0x7465_6462_7974_6573,
+      tail: 0,
+      ntail: 0,
+      len: 0,
+    }
+  }
+
+  #[inline]
+  const fn round(mut self) -> Self {
+    self.v0 = self.v0.wrapping_add(self.v1);
+    self.v1 = self.v1.rotate_left(13);
+    self.v1 ^= self.v0;
+    self.v0 = self.v0.rotate_left(32);
+    self.v2 = self.v2.wrapping_add(self.v3);
+    self.v3 = self.v3.rotate_left(16);
+    self.v3 ^= self.v2;
+    self.v0 = self.v0.wrapping_add(self.v3);
+    self.v3 = self.v3.rotate_left(21);
+    self.v3 ^= self.v0;
+    self.v2 = self.v2.wrapping_add(self.v1);
+    self.v1 = self.v1.rotate_left(17);
+    self.v1 ^= self.v2;
+    self.v2 = self.v2.rotate_left(32);
+    self
+  }
+
+  #[inline]
+  const fn compress(mut self, m: u64) -> Self {
+    self.v3 ^= m;
+    self = self.round().round();
+    self.v0 ^= m;
+    self
+  }
+
+  /// Feeds `bytes` into the hasher.
+  pub(crate) const fn write(mut self, bytes: &[u8]) -> Self {
+    let mut i = 0;
+    while i < bytes.len() {
+      self.tail |= (bytes[i] as u64) << (8 * self.ntail);
+      self.ntail += 1;
+      if self.ntail == 8 {
+        self = self.compress(self.tail);
+        self.tail = 0;
+        self.ntail = 0;
+      }
+      i += 1;
+    }
+    self.len = self.len.wrapping_add(bytes.len());
+    self
+  }
+
+  /// Feeds a `u64` into the hasher as 8 little-endian bytes.
+  #[inline]
+  pub(crate) const fn write_u64(self, n: u64) -> Self {
+    self.write(&n.to_le_bytes())
+  }
+
+  /// Finalizes the hash and returns the 64-bit output.
+  pub(crate) const fn finish(self) -> u64 {
+    let b = ((self.len as u64 & 0xff) << 56) | self.tail;
+    let mut s = self.compress(b);
+    s.v2 ^= 0xff;
+    s = s.round().round().round().round();
+    s.v0 ^ s.v1 ^ s.v2 ^ s.v3
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const KEY: [u8; 16] = [
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+  ];
+
+  /// Vectors from the SipHash reference implementation (`vectors.h`),
+  /// key `00..0f` and message `00..len-1`.
+  #[test]
+  fn reference_vectors() {
+    let msg: [u8; 64] = core::array::from_fn(|i| i as u8);
+    let cases: [(usize, u64); 3] = [
+      (0, 0x726f_db47_dd0e_0e31),
+      (1, 0x74f8_39c5_93dc_67fd),
+      (63, 0x958a_324c_eb06_4572),
+    ];
+    for (len, expected) in cases {
+      assert_eq!(
+        SipHasher24::new(&KEY).write(&msg[..len]).finish(),
+        expected,
+        "len {len}"
+      );
+    }
+  }
+
+  /// Splitting the input across several `write` calls must not change
+  /// the output, and must agree with the (deprecated, but still
+  /// SipHash-2-4) hasher in `core` for every length up to 64 bytes.
+  #[test]
+  #[allow(deprecated)]
+  fn matches_core_siphasher() {
+    use core::hash::{Hasher, SipHasher};
+
+    let msg: [u8; 64] = core::array::from_fn(|i| (i * 7 + 3) as u8);
+    for len in 0..=msg.len() {
+      let mut core_hasher = SipHasher::new_with_keys(0x0706_0504_0302_0100, 0x0f0e_0d0c_0b0a_0908);
+      core_hasher.write(&msg[..len]);
+      let expected = core_hasher.finish();
+
+      assert_eq!(SipHasher24::new(&KEY).write(&msg[..len]).finish(), expected);
+
+      let (a, b) = msg[..len].split_at(len / 3);
+      assert_eq!(SipHasher24::new(&KEY).write(a).write(b).finish(), expected);
+    }
+  }
+}
@@ -0,0 +1,448 @@
+//! Network interface discovery from Linux sysfs.
+//!
+//! Reads the `address`, `addr_assign_type`, `type` and `ifindex` attributes
+//! of each device in `/sys/class/net`. The sysfs root is configurable, so a
+//! fixture directory can stand in for `/sys`.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use hardware_address::sysfs::Sysfs;
+//!
+//! let sysfs = Sysfs::new();
+//! if let Some(mac) = sysfs.mac_addr("eth0")? {
+//!   println!("eth0 is {mac}");
+//! }
+//! for iface in sysfs.interfaces()? {
+//!   println!("{}: {:?} {:?}", iface.name(), iface.hardware_addr(), iface.assign_type());
+//! }
+//! # Ok::<_, std::io::Error>(())
+//! ```
+
+use std::{
+  fs, io,
+  path::{Path, PathBuf},
+  string::String,
+  vec::Vec,
+};
+
+pub use crate::netlink::Arphrd;
+
+use crate::{
+  hex::{decode_hex, HexError},
+  AnyHardwareAddr, MacAddr,
+};
+
+/// How the kernel assigned a device address (`NET_ADDR_*`), as in the
+/// `addr_assign_type` attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AddrAssignType {
+  /// The address is permanent, usually burned into the hardware.
+  Permanent,
+  /// The address was randomly generated.
+  Random,
+  /// The address was taken from another device, as bonds and bridges do.
+  Stolen,
+  /// The address was set from user space.
+  Set,
+  /// Any other assignment type.
+  Other(u8),
+}
+
+impl AddrAssignType {
+  /// Returns the assignment type with value `value`.
+  #[inline]
+  pub const fn from_u8(value: u8) -> Self {
+    match value {
+      0 => Self::Permanent,
+      1 => Self::Random,
+      2 => Self::Stolen,
+      3 => Self::Set,
+      other => Self::Other(other),
+    }
+  }
+
+  /// Returns the value of the assignment type.
+  #[inline]
+  pub const fn to_u8(self) -> u8 {
+    match self {
+      Self::Permanent => 0,
+      Self::Random => 1,
+      Self::Stolen => 2,
+      Self::Set => 3,
+      Self::Other(other) => other,
+    }
+  }
+
+  /// Returns `true` if the address is permanent.
+  #[inline]
+  pub const fn is_permanent(&self) -> bool {
+    matches!(self, Self::Permanent)
+  }
+
+  /// Returns `true` if the address was randomly generated.
+  #[inline]
+  pub const fn is_random(&self) -> bool {
+    matches!(self, Self::Random)
+  }
+}
+
+impl From<u8> for AddrAssignType {
+  #[inline]
+  fn from(value: u8) -> Self {
+    Self::from_u8(value)
+  }
+}
+
+impl From<AddrAssignType> for u8 {
+  #[inline]
+  fn from(value: AddrAssignType) -> Self {
+    value.to_u8()
+  }
+}
+
+/// A network interface read from sysfs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Interface {
+  name: String,
+  index: u32,
+  arphrd: Arphrd,
+  addr: Option<AnyHardwareAddr>,
+  assign_type: Option<AddrAssignType>,
+}
+
+impl Interface {
+  /// Returns the interface name.
+  #[inline]
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Returns the interface index.
+  #[inline]
+  pub const fn index(&self) -> u32 {
+    self.index
+  }
+
+  /// Returns the device type.
+  #[inline]
+  pub const fn arphrd(&self) -> Arphrd {
+    self.arphrd
+  }
+
+  /// Returns the device address, or `None` if the device has none or it is
+  /// not of a supported size, as for IPIP tunnels.
+  #[inline]
+  pub const fn hardware_addr(&self) -> Option<AnyHardwareAddr> {
+    self.addr
+  }
+
+  /// Returns the device address if it is a MAC address.
+  #[inline]
+  pub const fn mac_addr(&self) -> Option<MacAddr> {
+    match self.addr {
+      Some(AnyHardwareAddr::Mac(addr)) => Some(addr),
+      _ => None,
+    }
+  }
+
+  /// Returns how the address was assigned, or `None` if the kernel does not
+  /// report it for this device.
+  #[inline]
+  pub const fn assign_type(&self) -> Option<AddrAssignType> {
+    self.assign_type
+  }
+}
+
+/// A reader of network interfaces under a sysfs root.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Sysfs {
+  root: PathBuf,
+}
+
+impl Default for Sysfs {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Sysfs {
+  /// Creates a reader of `/sys`.
+  #[inline]
+  pub fn new() -> Self {
+    Self {
+      root: PathBuf::from("/sys"),
+    }
+  }
+
+  /// Sets the sysfs root, the directory holding `class/net`.
+  #[inline]
+  pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+    self.root = root.into();
+    self
+  }
+
+  /// Returns the sysfs root.
+  #[inline]
+  pub fn root(&self) -> &Path {
+    &self.root
+  }
+
+  /// Returns the directory of network devices.
+  #[inline]
+  fn class_net(&self) -> PathBuf {
+    self.root.join("class/net")
+  }
+
+  /// Reads the interface `name`.
+  pub fn interface(&self, name: &str) -> io::Result<Interface> {
+    let dir = self.class_net().join(name);
+    let index = read_attr(&dir, "ifindex")?;
+    let arphrd = Arphrd::from_u16(read_attr(&dir, "type")?);
+    let addr = parse_addr(fs::read_to_string(dir.join("address"))?.trim())?;
+    // Reading `addr_assign_type` fails with EINVAL for devices without an
+    // address.
+    let assign_type = read_attr(&dir, "addr_assign_type")
+      .ok()
+      .map(AddrAssignType::from_u8);
+    Ok(Interface {
+      name: name.into(),
+      index,
+      arphrd,
+      addr,
+      assign_type,
+    })
+  }
+
+  /// Reads every interface, ordered by index.
+  ///
+  /// Entries that are not directories, such as `bonding_masters`, and
+  /// interfaces removed while reading are skipped. Any other error, including
+  /// a malformed attribute of a single device, fails the whole listing.
+  pub fn interfaces(&self) -> io::Result<Vec<Interface>> {
+    let mut interfaces = Vec::new();
+    for entry in fs::read_dir(self.class_net())? {
+      let entry = entry?;
+      let name = match entry.file_name().into_string() {
+        Ok(name) => name,
+        Err(_) => continue,
+      };
+      // Devices are symlinks to their directories, so follow them.
+      match fs::metadata(entry.path()) {
+        Ok(meta) if meta.is_dir() => {}
+        Ok(_) => continue,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+        Err(e) => return Err(e),
+      }
+      match self.interface(&name) {
+        Ok(iface) => interfaces.push(iface),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+      }
+    }
+    interfaces.sort_by_key(Interface::index);
+    Ok(interfaces)
+  }
+
+  /// Reads the address of the interface `name`.
+  pub fn hardware_addr(&self, name: &str) -> io::Result<Option<AnyHardwareAddr>> {
+    parse_addr(fs::read_to_string(self.class_net().join(name).join("address"))?.trim())
+  }
+
+  /// Reads the address of the interface `name` if it is a MAC address.
+  pub fn mac_addr(&self, name: &str) -> io::Result<Option<MacAddr>> {
+    Ok(match self.hardware_addr(name)? {
+      Some(AnyHardwareAddr::Mac(addr)) => Some(addr),
+      _ => None,
+    })
+  }
+}
+
+/// Reads a numeric attribute.
+fn read_attr<T: core::str::FromStr>(dir: &Path, attr: &str) -> io::Result<T> {
+  let text = fs::read_to_string(dir.join(attr))?;
+  text.trim().parse().map_err(|_| {
+    io::Error::new(
+      io::ErrorKind::InvalidData,
+      std::format!("invalid {attr}: {:?}", text.trim()),
+    )
+  })
+}
+
+/// Parses an `address` attribute, colon-separated hex octets of any
+/// length up to `MAX_ADDR_LEN`.
+fn parse_addr(text: &str) -> io::Result<Option<AnyHardwareAddr>> {
+  let mut buf = [0u8; 32];
+  match decode_hex(text, &mut buf) {
+    Ok(len) => Ok(AnyHardwareAddr::from_bytes(&buf[..len])),
+    Err(HexError::InvalidText(_)) | Err(HexError::TooLong(_)) => Err(io::Error::new(
+      io::ErrorKind::InvalidData,
+      std::format!("invalid address: {text:?}"),
+    )),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::InfiniBandAddr;
+
+  /// A sysfs fixture in a temporary directory, removed on drop.
+  struct Fixture(PathBuf);
+
+  impl Fixture {
+    fn new(test: &str) -> Self {
+      let root = std::env::temp_dir().join(std::format!(
+        "hardware-address-sysfs-{}-{test}",
+        std::process::id()
+      ));
+      let _ = fs::remove_dir_all(&root);
+      fs::create_dir_all(root.join("class/net")).unwrap();
+      Self(root)
+    }
+
+    fn device(&self, name: &str, attrs: &[(&str, &str)]) -> &Self {
+      let dir = self.0.join("class/net").join(name);
+      fs::create_dir_all(&dir).unwrap();
+      for (attr, value) in attrs {
+        fs::write(dir.join(attr), std::format!("{value}\n")).unwrap();
+      }
+      self
+    }
+
+    fn sysfs(&self) -> Sysfs {
+      Sysfs::new().with_root(&self.0)
+    }
+  }
+
+  impl Drop for Fixture {
+    fn drop(&mut self) {
+      let _ = fs::remove_dir_all(&self.0);
+    }
+  }
+
+  #[test]
+  fn interfaces() {
+    let ib = "80:00:02:08:fe:80:00:00:00:00:00:00:00:02:c9:03:00:0a:0b:0c";
+    let fixture = Fixture::new("interfaces");
+    fixture
+      .device(
+        "wlan0",
+        &[
+          ("ifindex", "3"),
+          ("type", "1"),
+          ("address", "02:00:5e:00:53:01"),
+          ("addr_assign_type", "1"),
+        ],
+      )
+      .device(
+        "eth0",
+        &[
+          ("ifindex", "2"),
+          ("type", "1"),
+          ("address", "00:00:5e:00:53:01"),
+          ("addr_assign_type", "0"),
+        ],
+      )
+      .device(
+        "lo",
+        &[
+          ("ifindex", "1"),
+          ("type", "772"),
+          ("address", "00:00:00:00:00:00"),
+          ("addr_assign_type", "0"),
+        ],
+      )
+      .device("ib0", &[("ifindex", "5"), ("type", "32"), ("address", ib)])
+      .device(
+        "wg0",
+        &[("ifindex", "4"), ("type", "65534"), ("address", "")],
+      );
+    // The bonding driver adds a regular file next to the devices.
+    fs::write(fixture.0.join("class/net/bonding_masters"), "bond0\n").unwrap();
+    let sysfs = fixture.sysfs();
+
+    let interfaces = sysfs.interfaces().unwrap();
+    let names: Vec<&str> = interfaces.iter().map(Interface::name).collect();
+    assert_eq!(names, ["lo", "eth0", "wlan0", "wg0", "ib0"]);
+
+    let eth0 = &interfaces[1];
+    assert_eq!(eth0.index(), 2);
+    assert_eq!(eth0.arphrd(), Arphrd::Ether);
+    assert_eq!(
+      eth0.mac_addr(),
+      Some(MacAddr::from_raw([0x00, 0x00, 0x5e, 0x00, 0x53, 0x01]))
+    );
+    assert!(eth0.assign_type().unwrap().is_permanent());
+    assert!(interfaces[2].assign_type().unwrap().is_random());
+
+    assert_eq!(interfaces[0].arphrd(), Arphrd::Loopback);
+    assert_eq!(interfaces[3].arphrd(), Arphrd::None);
+    assert_eq!(interfaces[3].hardware_addr(), None);
+    assert_eq!(interfaces[3].assign_type(), None);
+
+    let ib0 = &interfaces[4];
+    assert_eq!(ib0.arphrd(), Arphrd::Infiniband);
+    assert_eq!(
+      ib0.hardware_addr(),
+      Some(AnyHardwareAddr::InfiniBand(
+        ib.parse::<InfiniBandAddr>().unwrap()
+      ))
+    );
+    assert_eq!(ib0.mac_addr(), None);
+
+    assert_eq!(sysfs.mac_addr("eth0").unwrap(), eth0.mac_addr());
+    assert_eq!(sysfs.mac_addr("ib0").unwrap(), None);
+    assert_eq!(
+      sysfs.interface("eth1").unwrap_err().kind(),
+      io::ErrorKind::NotFound
+    );
+  }
+
+  #[test]
+  fn malformed() {
+    let fixture = Fixture::new("malformed");
+    fixture
+      .device(
+        "tunl0",
+        &[
+          ("ifindex", "1"),
+          ("type", "768"),
+          ("address", "00:00:00:00"),
+        ],
+      )
+      .device(
+        "bad0",
+        &[("ifindex", "2"), ("type", "1"), ("address", "zz:00")],
+      );
+    let sysfs = fixture.sysfs();
+
+    let tunl0 = sysfs.interface("tunl0").unwrap();
+    assert_eq!(tunl0.arphrd(), Arphrd::Tunnel);
+    assert_eq!(tunl0.hardware_addr(), None);
+
+    assert_eq!(
+      sysfs.interface("bad0").unwrap_err().kind(),
+      io::ErrorKind::InvalidData
+    );
+    // One malformed device fails the whole listing.
+    assert_eq!(
+      sysfs.interfaces().unwrap_err().kind(),
+      io::ErrorKind::InvalidData
+    );
+  }
+
+  #[test]
+  fn conversions() {
+    for value in [1, 32, 772, 65534, 9999] {
+      assert_eq!(Arphrd::from_u16(value).to_u16(), value);
+    }
+    assert_eq!(Arphrd::from(9999), Arphrd::Other(9999));
+    for value in 0..=5 {
+      assert_eq!(AddrAssignType::from(value).to_u8(), value);
+    }
+    assert_eq!(AddrAssignType::from_u8(2), AddrAssignType::Stolen);
+  }
+}